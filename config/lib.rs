@@ -5,15 +5,26 @@
 //! GearConfig is used in formal code, see state.rs.
 //!
 //! For testing purpose, MockConfig is preferred, see contract_panic_test.rs and contract_test.rs
+//!
+//! MockConfig128 has the balance width of GearConfig, see contract_invariant_test.rs
 
 use ::parity_scale_codec::{Decode, Encode};
 use ::scale_info::TypeInfo;
-use gstd::{prelude::*, ActorId};
+use gstd::{prelude::*, ActorId, MessageId};
 use interface::*;
+use io::Signature;
+
+/// signing context used by substrate wallets for sr25519 signatures
+const SR25519_CONTEXT: &[u8] = b"substrate";
 
 /// GearConfig implements IConfig for gear environment
-#[derive(Default, Clone, Copy, Encode, Decode, TypeInfo)]
-pub struct GearConfig;
+#[derive(Default, Clone, Encode, Decode, TypeInfo)]
+pub struct GearConfig {
+    /// signers of the meta-transactions in progress, keyed by the message
+    /// executing them, since other messages may be handled while one awaits
+    /// a reply
+    signers: Vec<(MessageId, ActorId)>,
+}
 
 impl IConfig for GearConfig {
     type AccountId = ActorId;
@@ -21,20 +32,86 @@ impl IConfig for GearConfig {
     type TokenId = u128;
     type Text = String;
     fn sender(&self) -> Self::AccountId {
-        gstd::msg::source()
+        let id = gstd::msg::id();
+        self.signers
+            .iter()
+            .find(|(message, _)| *message == id)
+            .map(|(_, signer)| *signer)
+            .unwrap_or_else(gstd::msg::source)
+    }
+    fn origin(&self) -> Self::AccountId {
+        gstd::exec::origin()
+    }
+    fn set_signer(&mut self, signer: Option<Self::AccountId>) {
+        let id = gstd::msg::id();
+        self.signers.retain(|(message, _)| *message != id);
+        if let Some(signer) = signer {
+            self.signers.push((id, signer));
+        }
+    }
+    fn value(&self) -> Self::Balance {
+        gstd::msg::value()
+    }
+    fn program_id(&self) -> Self::AccountId {
+        gstd::exec::program_id()
+    }
+    fn block_timestamp(&self) -> u64 {
+        gstd::exec::block_timestamp()
+    }
+    fn block_height(&self) -> u32 {
+        gstd::exec::block_height()
+    }
+    // account ids are the public keys of their owners
+    fn verify(&self, signer: Self::AccountId, message: &[u8], signature: Signature) -> bool {
+        match signature {
+            Signature::Sr25519(signature) => {
+                let (Ok(public), Ok(signature)) = (
+                    schnorrkel::PublicKey::from_bytes(signer.as_ref()),
+                    schnorrkel::Signature::from_bytes(&signature),
+                ) else {
+                    return false;
+                };
+                public
+                    .verify_simple(SR25519_CONTEXT, message, &signature)
+                    .is_ok()
+            }
+            Signature::Ed25519(signature) => {
+                use ed25519_dalek::Verifier;
+                let (Ok(public), Ok(signature)) = (
+                    ed25519_dalek::PublicKey::from_bytes(signer.as_ref()),
+                    ed25519_dalek::Signature::from_bytes(&signature),
+                ) else {
+                    return false;
+                };
+                public.verify(message, &signature).is_ok()
+            }
+        }
     }
 }
 
-/// GearConfig implements IConfig for testing environment
+/// MockConfig implements IConfig for testing environment
 #[derive(Default, Clone, Copy, PartialOrd, Eq, PartialEq)]
 pub struct MockConfig {
     pub sender: u8,
+    pub origin: u8,
+    pub signer: Option<u8>,
+    pub value: u32,
+    pub program_id: u8,
+    pub block_timestamp: u64,
+    pub block_height: u32,
 }
 
 impl MockConfig {
     pub fn set_sender(&mut self, sender: u8) {
         self.sender = sender;
     }
+    /// mock signature accepted by `verify`: the signer followed by the message
+    pub fn sign(signer: u8, message: &[u8]) -> Signature {
+        let mut signature = [0; 64];
+        signature[0] = signer;
+        signature[1..=message.len()].copy_from_slice(message);
+        Signature::Sr25519(signature)
+    }
 }
 
 impl IConfig for MockConfig {
@@ -43,6 +120,89 @@ impl IConfig for MockConfig {
     type TokenId = u8;
     type Text = String;
     fn sender(&self) -> Self::AccountId {
-        self.sender
+        self.signer.unwrap_or(self.sender)
+    }
+    fn origin(&self) -> Self::AccountId {
+        self.origin
+    }
+    fn set_signer(&mut self, signer: Option<Self::AccountId>) {
+        self.signer = signer;
+    }
+    fn value(&self) -> Self::Balance {
+        self.value
+    }
+    fn program_id(&self) -> Self::AccountId {
+        self.program_id
+    }
+    fn block_timestamp(&self) -> u64 {
+        self.block_timestamp
+    }
+    fn block_height(&self) -> u32 {
+        self.block_height
+    }
+    fn verify(&self, signer: Self::AccountId, message: &[u8], signature: Signature) -> bool {
+        let (Signature::Sr25519(signature) | Signature::Ed25519(signature)) = signature;
+        signature[0] == signer && signature[1..].starts_with(message)
+    }
+}
+
+/// MockConfig128 implements IConfig for testing with the account and balance
+/// widths of GearConfig
+#[derive(Default, Clone, Copy, PartialOrd, Eq, PartialEq)]
+pub struct MockConfig128 {
+    pub sender: [u8; 32],
+    pub origin: [u8; 32],
+    pub signer: Option<[u8; 32]>,
+    pub value: u128,
+    pub program_id: [u8; 32],
+    pub block_timestamp: u64,
+    pub block_height: u32,
+}
+
+impl MockConfig128 {
+    pub fn set_sender(&mut self, sender: [u8; 32]) {
+        self.sender = sender;
+    }
+    /// mock signature accepted by `verify`: the signer followed by the
+    /// first 32 bytes of the message
+    pub fn sign(signer: [u8; 32], message: &[u8]) -> Signature {
+        let mut signature = [0; 64];
+        signature[..32].copy_from_slice(&signer);
+        let len = message.len().min(32);
+        signature[32..32 + len].copy_from_slice(&message[..len]);
+        Signature::Sr25519(signature)
+    }
+}
+
+impl IConfig for MockConfig128 {
+    type AccountId = [u8; 32];
+    type Balance = u128;
+    type TokenId = u128;
+    type Text = String;
+    fn sender(&self) -> Self::AccountId {
+        self.signer.unwrap_or(self.sender)
+    }
+    fn origin(&self) -> Self::AccountId {
+        self.origin
+    }
+    fn set_signer(&mut self, signer: Option<Self::AccountId>) {
+        self.signer = signer;
+    }
+    fn value(&self) -> Self::Balance {
+        self.value
+    }
+    fn program_id(&self) -> Self::AccountId {
+        self.program_id
+    }
+    fn block_timestamp(&self) -> u64 {
+        self.block_timestamp
+    }
+    fn block_height(&self) -> u32 {
+        self.block_height
+    }
+    fn verify(&self, signer: Self::AccountId, message: &[u8], signature: Signature) -> bool {
+        let (Signature::Sr25519(signature) | Signature::Ed25519(signature)) = signature;
+        let len = message.len().min(32);
+        signature[..32] == signer && signature[32..32 + len] == message[..len]
     }
 }
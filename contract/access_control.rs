@@ -0,0 +1,49 @@
+//! contract implementation
+
+use crate::*;
+
+/// IAccessControl interface
+impl<T: IConfig> IAccessControl<T> for Contract<T> {
+    fn owner(&self) -> T::AccountId {
+        self.owner
+    }
+    fn has_role(&self, account: T::AccountId, role: Role) -> bool {
+        account == self.owner
+            || self
+                .roles
+                .get(&account)
+                .map(|roles| roles.contains(&role))
+                .unwrap_or(false)
+    }
+    fn grant_role(&mut self, account: T::AccountId, role: Role) -> Result<(), Error> {
+        self.check_grant_role(account, role)?;
+        self.roles.entry(account).or_default().insert(role);
+        Ok(())
+    }
+    fn revoke_role(&mut self, account: T::AccountId, role: Role) -> Result<(), Error> {
+        self.check_revoke_role(account, role)?;
+        self.remove_role(account, role);
+        Ok(())
+    }
+    fn renounce_role(&mut self, account: T::AccountId, role: Role) -> Result<(), Error> {
+        self.check_renounce_role(account, role)?;
+        self.remove_role(account, role);
+        Ok(())
+    }
+    fn transfer_ownership(&mut self, new_owner: T::AccountId) -> Result<(), Error> {
+        self.check_transfer_ownership(new_owner)?;
+        self.owner = new_owner;
+        Ok(())
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    fn remove_role(&mut self, account: T::AccountId, role: Role) {
+        if let Some(roles) = self.roles.get_mut(&account) {
+            roles.remove(&role);
+            if roles.is_empty() {
+                self.roles.remove(&account);
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! contract invariant tests
+//!
+//! random sequences of actions from a few accounts are run against a
+//! `Contract<MockConfig128>`, which has the balance width of `GearConfig`,
+//! checking the invariants after every step
+
+use crate::*;
+use config::*;
+use proptest::prelude::*;
+
+type Account = <MockConfig128 as IConfig>::AccountId;
+
+/// owner of the contract, holds every role
+const OWNER: u8 = 1;
+
+fn account(n: u8) -> Account {
+    [n; 32]
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Mint {
+        sender: u8,
+        to: u8,
+        token: u128,
+        amount: u128,
+    },
+    Transfer {
+        sender: u8,
+        from: u8,
+        to: u8,
+        token: u128,
+        amount: u128,
+    },
+    Burn {
+        sender: u8,
+        from: u8,
+        token: u128,
+        amount: u128,
+    },
+    SetApprovalForAll {
+        sender: u8,
+        operator: u8,
+        approved: bool,
+    },
+    Approve {
+        sender: u8,
+        operator: u8,
+        token: u128,
+        amount: u128,
+    },
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let actor = 0u8..5;
+    let token = 0u128..3;
+    let amount = prop_oneof![
+        0u128..20,
+        0u128..=u64::MAX as u128,
+        u128::MAX - 20..=u128::MAX
+    ];
+    prop_oneof![
+        (actor.clone(), actor.clone(), token.clone(), amount.clone()).prop_map(
+            |(sender, to, token, amount)| Op::Mint {
+                sender,
+                to,
+                token,
+                amount
+            }
+        ),
+        (
+            actor.clone(),
+            actor.clone(),
+            actor.clone(),
+            token.clone(),
+            amount.clone()
+        )
+            .prop_map(|(sender, from, to, token, amount)| Op::Transfer {
+                sender,
+                from,
+                to,
+                token,
+                amount
+            }),
+        (actor.clone(), actor.clone(), token.clone(), amount.clone()).prop_map(
+            |(sender, from, token, amount)| Op::Burn {
+                sender,
+                from,
+                token,
+                amount
+            }
+        ),
+        (actor.clone(), actor.clone(), any::<bool>()).prop_map(|(sender, operator, approved)| {
+            Op::SetApprovalForAll {
+                sender,
+                operator,
+                approved,
+            }
+        }),
+        (actor.clone(), actor, token, amount).prop_map(|(sender, operator, token, amount)| {
+            Op::Approve {
+                sender,
+                operator,
+                token,
+                amount,
+            }
+        }),
+    ]
+}
+
+fn contract() -> Contract<MockConfig128> {
+    Contract::<MockConfig128> {
+        owner: account(OWNER),
+        mint_policies: BTreeMap::from([
+            (0, MintPolicy::Unlimited),
+            (1, MintPolicy::Unlimited),
+            (2, MintPolicy::Fixed),
+        ]),
+        ..Default::default()
+    }
+}
+
+/// whether `operator` may move `amount` of the token of `owner` without
+/// being its owner, before the action is applied
+fn authorized(
+    contract: &Contract<MockConfig128>,
+    owner: Account,
+    operator: Account,
+    token: u128,
+    amount: u128,
+) -> bool {
+    owner == operator
+        || contract.is_approved_for_all(owner, operator)
+        || contract.allowance(owner, operator, token) >= amount && amount > 0
+}
+
+/// a failed action must leave the state untouched
+fn assert_unchanged(
+    before: &Contract<MockConfig128>,
+    after: &Contract<MockConfig128>,
+) -> Result<(), TestCaseError> {
+    prop_assert_eq!(&before.balances, &after.balances);
+    prop_assert_eq!(&before.owned_tokens, &after.owned_tokens);
+    prop_assert_eq!(&before.total_supply, &after.total_supply);
+    prop_assert_eq!(&before.approvals, &after.approvals);
+    prop_assert_eq!(&before.allowances, &after.allowances);
+    Ok(())
+}
+
+fn assert_invariants(
+    contract: &Contract<MockConfig128>,
+    supply: &BTreeMap<u128, u128>,
+) -> Result<(), TestCaseError> {
+    for token in 0..3 {
+        // balances add up to the total supply, which only mints and burns
+        // change
+        let sum = contract
+            .balances
+            .get(&token)
+            .map(|kv| kv.values().sum())
+            .unwrap_or(0u128);
+        let expected = supply.get(&token).copied().unwrap_or_default();
+        prop_assert_eq!(sum, contract.total_supply(token));
+        prop_assert_eq!(sum, expected);
+        for n in 0..5 {
+            // owned tokens are exactly the ones with a positive balance
+            let owned = contract
+                .owned_tokens
+                .get(&account(n))
+                .is_some_and(|tokens| tokens.contains(&token));
+            prop_assert_eq!(owned, contract.balance_of(account(n), token) > 0);
+        }
+    }
+    prop_assert!(!contract.balances.contains_key(&3));
+    Ok(())
+}
+
+fn apply(
+    contract: &mut Contract<MockConfig128>,
+    supply: &mut BTreeMap<u128, u128>,
+    op: Op,
+) -> Result<(), TestCaseError> {
+    let before = contract.clone();
+    let result = match op {
+        Op::Mint {
+            sender,
+            to,
+            token,
+            amount,
+        } => {
+            contract.ctx.set_sender(account(sender));
+            let result = contract.mint(account(to), token, amount);
+            if result.is_ok() {
+                prop_assert_eq!(sender, OWNER);
+                *supply.entry(token).or_default() += amount;
+            }
+            result
+        }
+        Op::Transfer {
+            sender,
+            from,
+            to,
+            token,
+            amount,
+        } => {
+            contract.ctx.set_sender(account(sender));
+            let (from, to, sender) = (account(from), account(to), account(sender));
+            let approved = contract.is_approved_for_all(from, sender);
+            let allowance = contract.allowance(from, sender, token);
+            let result = contract.safe_transfer_from(from, to, token, amount);
+            if result.is_ok() {
+                prop_assert!(authorized(&before, from, sender, token, amount));
+                prop_assert_eq!(
+                    contract.balance_of(to, token),
+                    before.balance_of(to, token) + amount
+                );
+                if from != sender && !approved {
+                    prop_assert_eq!(contract.allowance(from, sender, token), allowance - amount);
+                }
+            }
+            result
+        }
+        Op::Burn {
+            sender,
+            from,
+            token,
+            amount,
+        } => {
+            contract.ctx.set_sender(account(sender));
+            let result = contract.burn(account(from), token, amount);
+            if result.is_ok() {
+                prop_assert!(authorized(
+                    &before,
+                    account(from),
+                    account(sender),
+                    token,
+                    amount
+                ));
+                *supply.entry(token).or_default() -= amount;
+            }
+            result
+        }
+        Op::SetApprovalForAll {
+            sender,
+            operator,
+            approved,
+        } => {
+            contract.ctx.set_sender(account(sender));
+            let result =
+                contract.set_approval_for_all(account(sender), account(operator), approved);
+            if result.is_ok() {
+                prop_assert_eq!(
+                    contract.is_approved_for_all(account(sender), account(operator)),
+                    approved
+                );
+            }
+            result
+        }
+        Op::Approve {
+            sender,
+            operator,
+            token,
+            amount,
+        } => {
+            contract.ctx.set_sender(account(sender));
+            let result = contract.approve(account(sender), account(operator), token, amount);
+            if result.is_ok() {
+                prop_assert_eq!(
+                    contract.allowance(account(sender), account(operator), token),
+                    amount
+                );
+            }
+            result
+        }
+    };
+    if result.is_err() {
+        assert_unchanged(&before, contract)?;
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn invariants_hold_for_random_actions(ops in proptest::collection::vec(op(), 1..64)) {
+        let mut contract = contract();
+        let mut supply = BTreeMap::new();
+        for op in ops {
+            apply(&mut contract, &mut supply, op)?;
+            assert_invariants(&contract, &supply)?;
+        }
+    }
+
+    #[test]
+    fn owner_mints_are_conserved_by_transfers(
+        amount in 1u128..=u64::MAX as u128,
+        transfers in proptest::collection::vec((1u8..5, 1u8..5, 1u128..=u64::MAX as u128), 0..32),
+    ) {
+        let mut contract = contract();
+        let mut supply = BTreeMap::new();
+        apply(&mut contract, &mut supply, Op::Mint { sender: OWNER, to: OWNER, token: 0, amount })?;
+        prop_assert_eq!(contract.balance_of(account(OWNER), 0), amount);
+        for (from, to, amount) in transfers {
+            apply(&mut contract, &mut supply, Op::Transfer { sender: from, from, to, token: 0, amount })?;
+            assert_invariants(&contract, &supply)?;
+        }
+    }
+}
@@ -4,26 +4,26 @@ use crate::*;
 use config::*;
 
 #[test]
-#[should_panic]
-fn mint_twice_panics() {
+fn mint_twice_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
-    contract.mint(1, 2, 3); // to, token, amount
-    contract.mint(1, 2, 3);
-    panic!("this line shouldn't appear in cargo test result");
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    assert_eq!(contract.mint(1, 2, 3), Err(Error::AlreadyMinted));
 }
 
 #[test]
-#[should_panic]
-fn mint_batch_twice_panics() {
+fn mint_batch_twice_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
-    contract.mint_batch(1, vec![0, 1, 2], vec![1, 2, 3]); // to, token, amount
-    contract.mint_batch(1, vec![0, 1, 2], vec![1, 2, 3]);
-    panic!("this line shouldn't appear in cargo test result");
+    contract
+        .mint_batch(1, vec![0, 1, 2], vec![1, 2, 3])
+        .unwrap(); // to, token, amount
+    assert_eq!(
+        contract.mint_batch(1, vec![0, 1, 2], vec![1, 2, 3]),
+        Err(Error::AlreadyMinted)
+    );
 }
 
 #[test]
-#[should_panic]
-fn balance_of_batch_length_mismatch_panics() {
+fn balance_of_batch_length_mismatch_fails() {
     let contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([
             (0, BTreeMap::from([(1, 1)])),
@@ -33,61 +33,68 @@ fn balance_of_batch_length_mismatch_panics() {
         ..Default::default()
     };
 
-    contract.balance_of_batch(vec![1, 1, 1], vec![0, 1, 2, 3]);
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.balance_of_batch(vec![1, 1, 1], vec![0, 1, 2, 3]),
+        Err(Error::LengthMismatch)
+    );
 }
 
 #[test]
-#[should_panic]
-fn transfer_from_non_owner_panics() {
+fn transfer_from_non_owner_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(0, BTreeMap::from([(1, 1)]))]),
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_transfer_from(1, 42, 0, 1); // from, to, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.safe_transfer_from(1, 42, 0, 1),
+        Err(Error::NeedsApproval)
+    ); // from, to, token, amount
 }
 
 #[test]
-#[should_panic]
-fn transfer_exceeding_balance_panics() {
+fn transfer_exceeding_balance_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(0, BTreeMap::from([(1, 3)]))]),
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_transfer_from(1, 42, 0, 4); // from, to, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.safe_transfer_from(1, 42, 0, 4),
+        Err(Error::InsufficientBalance)
+    ); // from, to, token, amount
 }
 
 #[test]
-#[should_panic]
-fn transfer_batch_from_non_owner_panics() {
+fn transfer_batch_from_non_owner_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(0, BTreeMap::from([(1, 1)]))]),
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_batch_transfer_from(1, 42, vec![0], vec![1]); // from, to, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.safe_batch_transfer_from(1, 42, vec![0], vec![1]),
+        Err(Error::NeedsApproval)
+    ); // from, to, token, amount
 }
 
 #[test]
-#[should_panic]
-fn transfer_batch_exceeding_balance_panics() {
+fn transfer_batch_exceeding_balance_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([
             (0, BTreeMap::from([(1, 3)])),
@@ -97,17 +104,19 @@ fn transfer_batch_exceeding_balance_panics() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![4, 2, 1]); // from, to, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![4, 2, 1]),
+        Err(Error::InsufficientBalance)
+    ); // from, to, token, amount
 }
 
 #[test]
-#[should_panic]
-fn transfer_batch_length_mismatch_panics() {
+fn transfer_batch_length_mismatch_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([
             (0, BTreeMap::from([(1, 1)])),
@@ -117,45 +126,61 @@ fn transfer_batch_length_mismatch_panics() {
         ..Default::default()
     };
 
-    contract.safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![0, 1, 2, 3]);
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![0, 1, 2, 3]),
+        Err(Error::LengthMismatch)
+    );
 }
 
 #[test]
-#[should_panic]
-fn burn_from_non_owner_panics() {
+fn burn_from_non_owner_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn(1, 2, 3); // from, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(contract.burn(1, 2, 3), Err(Error::NeedsApproval)); // from, token, amount
 }
 
 #[test]
-#[should_panic]
-fn burn_exceeding_balance_panics() {
+fn burn_from_owner_without_approval_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 42,
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.burn(1, 2, 3), Err(Error::NeedsApproval)); // from, token, amount
+    assert_eq!(contract.balance_of(1, 2), 3);
+}
+
+#[test]
+fn burn_exceeding_balance_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn(1, 2, 4); // from, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(contract.burn(1, 2, 4), Err(Error::InsufficientBalance)); // from, token, amount
 }
 
 #[test]
-#[should_panic]
-fn burn_batch_length_mismatch_panics() {
+fn burn_batch_length_mismatch_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([
             (0, BTreeMap::from([(1, 1)])),
@@ -165,13 +190,14 @@ fn burn_batch_length_mismatch_panics() {
         ..Default::default()
     };
 
-    contract.burn_batch(1, vec![0, 1, 2], vec![0, 1, 2, 3]);
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.burn_batch(1, vec![0, 1, 2], vec![0, 1, 2, 3]),
+        Err(Error::LengthMismatch)
+    );
 }
 
 #[test]
-#[should_panic]
-fn burn_batch_from_non_owner_panics() {
+fn burn_batch_from_non_owner_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([
             (0, BTreeMap::from([(1, 4)])),
@@ -181,17 +207,19 @@ fn burn_batch_from_non_owner_panics() {
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn_batch(1, vec![0, 1, 2], vec![5, 5, 5]); // from, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.burn_batch(1, vec![0, 1, 2], vec![5, 5, 5]),
+        Err(Error::NeedsApproval)
+    ); // from, token, amount
 }
 
 #[test]
-#[should_panic]
-fn burn_batch_exceeding_balance_panics() {
+fn burn_batch_exceeding_balance_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([
             (0, BTreeMap::from([(1, 4)])),
@@ -201,47 +229,1145 @@ fn burn_batch_exceeding_balance_panics() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn_batch(1, vec![0, 1, 2], vec![5, 5, 5]); // from, token, amount
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.burn_batch(1, vec![0, 1, 2], vec![5, 5, 5]),
+        Err(Error::InsufficientBalance)
+    ); // from, token, amount
 }
 
 #[test]
-#[should_panic]
-fn set_approval_for_all_from_non_owner_panics() {
+fn set_approval_for_all_from_non_owner_fails() {
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
-    contract.set_approval_for_all(1, 42, true); // owner, operator
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.set_approval_for_all(1, 42, true),
+        Err(Error::NotAccountOwner)
+    ); // owner, operator
 }
 
 #[test]
-#[should_panic]
-fn update_token_metadata_from_non_owner_panics() {
+fn update_token_metadata_from_non_creator_fails() {
     let some_metadata = Some(TokenMetadata {
         name: "nft".to_string(),
         description: "nft for test".to_string(),
         image_uri: "https://gm.dev/nft.png".to_string(),
         json_uri: "https://gm.dev/nft.json".to_string(),
+        ..Default::default()
     });
 
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3), (42, 1)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.update_token_metadata(2, some_metadata),
+        Err(Error::NotTokenCreator)
+    );
+}
+
+#[test]
+fn update_token_metadata_with_invalid_fields_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let invalid = [
+        TokenMetadata {
+            name: "x".repeat(129),
+            ..Default::default()
+        },
+        TokenMetadata {
+            external_url: "x".repeat(513),
+            ..Default::default()
+        },
+        TokenMetadata {
+            attributes: vec![("k".to_string(), "v".to_string()); 65],
+            ..Default::default()
+        },
+        TokenMetadata {
+            attributes: vec![("k".to_string(), "v".repeat(129))],
+            ..Default::default()
+        },
+        TokenMetadata {
+            decimals: 39,
+            ..Default::default()
+        },
+    ];
+    for metadata in invalid {
+        assert_eq!(
+            contract.update_token_metadata(2, Some(metadata)),
+            Err(Error::InvalidMetadata)
+        );
+    }
+    assert!(contract.metadata_registry.is_empty());
+}
+
+#[test]
+fn update_frozen_metadata_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::MetadataAdmin]))]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        frozen_metadata: BTreeSet::from([2]),
+        ..Default::default()
+    };
+
+    for sender in [1, 42] {
+        contract.ctx.sender = sender;
+        assert_eq!(
+            contract.update_token_metadata(2, Some(TokenMetadata::default())),
+            Err(Error::MetadataFrozen)
+        );
+        assert_eq!(
+            contract.set_token_uri(2, Some("ipfs://nft/{id}".to_string())),
+            Err(Error::MetadataFrozen)
+        );
+        assert_eq!(contract.freeze_metadata(2), Err(Error::MetadataFrozen));
+    }
+}
+
+#[test]
+fn freeze_metadata_from_non_creator_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3), (42, 1)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.freeze_metadata(2), Err(Error::NotTokenCreator));
+    assert_eq!(contract.freeze_metadata(3), Err(Error::NoSuchToken));
+    assert!(!contract.is_metadata_frozen(2));
+}
+
+#[test]
+fn set_token_uri_from_non_creator_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3), (42, 1)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.set_token_uri(2, Some("ipfs://nft/{id}".to_string())),
+        Err(Error::NotTokenCreator)
+    );
+}
+
+#[test]
+fn mint_from_non_minter_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.mint(42, 2, 3),
+        Err(Error::MissingRole(Role::Minter))
+    ); // to, token, amount
+}
+
+#[test]
+fn grant_role_from_non_owner_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::Minter]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.grant_role(69, Role::Minter),
+        Err(Error::NotContractOwner)
+    ); // account, role
+}
+
+#[test]
+fn renounce_role_for_others_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::Minter]))]),
+        ctx: MockConfig {
+            sender: 69,
+            origin: 69,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.renounce_role(42, Role::Minter),
+        Err(Error::NotAccountOwner)
+    ); // account, role
+}
+
+#[test]
+fn transfer_ownership_from_non_owner_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.transfer_ownership(42),
+        Err(Error::NotContractOwner)
+    );
+}
+
+#[test]
+fn mint_capped_token_exceeding_max_supply_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract
+        .create_token(2, MintPolicy::Capped { max_supply: 5 })
+        .unwrap(); // token, policy
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    assert_eq!(contract.mint(1, 2, 3), Err(Error::MaxSupplyExceeded)); // to, token, amount
+}
+
+#[test]
+fn create_minted_token_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    assert_eq!(
+        contract.create_token(2, MintPolicy::Unlimited),
+        Err(Error::TokenAlreadyExists)
+    ); // token, policy
+}
+
+#[test]
+fn register_other_receiver_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.register_receiver(100), Err(Error::NotAccountOwner));
+}
+
+#[test]
+fn transfer_exceeding_allowance_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        allowances: BTreeMap::from([(1, BTreeMap::from([((42, 2), 1)]))]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.safe_transfer_from(1, 42, 2, 2),
+        Err(Error::InsufficientAllowance)
+    ); // from, to, token, amount
+}
+
+#[test]
+fn transfer_with_allowance_for_other_token_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        allowances: BTreeMap::from([(1, BTreeMap::from([((42, 3), 1)]))]),
         balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.safe_transfer_from(1, 42, 2, 1),
+        Err(Error::NeedsApproval)
+    ); // from, to, token, amount
+}
+
+#[test]
+fn approve_for_other_owner_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.approve(1, 42, 2, 1), Err(Error::NotAccountOwner)); // owner, operator, token, amount
+}
+
+#[test]
+fn subscribe_other_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.subscribe(42), Err(Error::NotContractOwner));
+    assert_eq!(contract.subscribe(7), Err(Error::NotContractOwner));
+}
+
+#[test]
+fn unsubscribe_other_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        subscribers: BTreeSet::from([7]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.unsubscribe(7), Err(Error::NotAccountOwner));
+    assert!(contract.is_subscriber(7));
+}
+
+#[test]
+fn subscribe_beyond_limit_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    for who in 1..=16 {
+        contract.subscribe(who).unwrap();
+    }
+
+    assert_eq!(contract.subscribe(17), Err(Error::TooManySubscribers));
+    contract.subscribe(16).unwrap();
+    assert_eq!(contract.subscribers.len(), 16);
+}
+
+#[test]
+fn pause_without_pauser_role_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.pause(), Err(Error::MissingRole(Role::Pauser)));
+}
+
+#[test]
+fn unpause_unpaused_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    assert_eq!(contract.unpause(), Err(Error::NotPaused));
+}
+
+#[test]
+fn mint_when_paused_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        paused: true,
+        ..Default::default()
+    };
+
+    assert_eq!(contract.mint(1, 2, 3), Err(Error::Paused)); // to, token, amount
+}
+
+#[test]
+fn transfer_when_paused_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        paused: true,
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 1)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.safe_transfer_from(1, 42, 2, 1), Err(Error::Paused)); // from, to, token, amount
+}
+
+#[test]
+fn transfer_batch_with_paused_token_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        paused_tokens: BTreeSet::from([3]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 1)])), (3, BTreeMap::from([(1, 1)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.safe_batch_transfer_from(1, 42, vec![2, 3], vec![1, 1]),
+        Err(Error::TokenPaused)
+    ); // from, to, token, amount
+    assert_eq!(contract.balance_of(1, 2), 1);
+}
+
+#[test]
+fn burn_paused_token_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        paused_tokens: BTreeSet::from([2]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 1)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.burn(1, 2, 1), Err(Error::TokenPaused)); // from, token, amount
+}
+
+#[test]
+fn transfer_batch_with_duplicate_ids_overdraw_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.update_token_metadata(2, some_metadata);
-    panic!("this line shouldn't appear in cargo test result");
+    assert_eq!(
+        contract.safe_batch_transfer_from(1, 42, vec![2, 2], vec![2, 2]),
+        Err(Error::InsufficientBalance)
+    ); // from, to, token, amount
+    assert_eq!(contract.balance_of(1, 2), 3);
+    assert_eq!(contract.balance_of(42, 2), 0);
+}
+
+#[test]
+fn burn_batch_with_zero_amount_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.burn_batch(1, vec![2, 2], vec![1, 0]),
+        Err(Error::ZeroAmount)
+    ); // from, token, amount
+}
+
+#[test]
+fn permit_for_all_after_deadline_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            block_timestamp: 300,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let message = contract.permit_message(1, 42, true, 200, 0); // owner, operator, approved, deadline, nonce
+    let signature = MockConfig::sign(1, &message);
+    assert_eq!(
+        contract.permit_for_all(1, 42, true, 200, 0, signature),
+        Err(Error::PermitExpired)
+    ); // owner, operator, approved, deadline, nonce, signature
+}
+
+#[test]
+fn permit_for_all_replay_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let message = contract.permit_message(1, 42, true, 200, 0); // owner, operator, approved, deadline, nonce
+    let signature = MockConfig::sign(1, &message);
+    contract
+        .permit_for_all(1, 42, true, 200, 0, signature)
+        .unwrap(); // owner, operator, approved, deadline, nonce, signature
+    assert_eq!(
+        contract.permit_for_all(1, 42, true, 200, 0, signature),
+        Err(Error::InvalidNonce)
+    ); // owner, operator, approved, deadline, nonce, signature
+}
+
+#[test]
+fn permit_for_all_signed_by_other_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let message = contract.permit_message(1, 42, true, 200, 0); // owner, operator, approved, deadline, nonce
+    let signature = MockConfig::sign(42, &message);
+    assert_eq!(
+        contract.permit_for_all(1, 42, true, 200, 0, signature),
+        Err(Error::InvalidSignature)
+    ); // owner, operator, approved, deadline, nonce, signature
+}
+
+#[test]
+fn permit_for_all_with_tampered_fields_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let message = contract.permit_message(1, 42, true, 200, 0); // owner, operator, approved, deadline, nonce
+    let signature = MockConfig::sign(1, &message);
+    assert_eq!(
+        contract.permit_for_all(1, 9, true, 200, 0, signature),
+        Err(Error::InvalidSignature)
+    ); // owner, operator, approved, deadline, nonce, signature
+    assert_eq!(
+        contract.permit_for_all(1, 42, true, 300, 0, signature),
+        Err(Error::InvalidSignature)
+    ); // owner, operator, approved, deadline, nonce, signature
+}
+
+#[test]
+fn execute_signed_by_other_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 1)]))]),
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let payload = b"transfer 1 of token 2 to 42";
+    let message = contract.signed_message(1, 0, 200, payload); // signer, nonce, deadline, payload
+    let signature = MockConfig::sign(42, &message);
+    assert_eq!(
+        contract.execute_signed(1, 0, 200, payload, signature, |contract| {
+            contract.safe_transfer_from(1, 42, 2, 1) // from, to, token, amount
+        }),
+        Err(Error::InvalidSignature)
+    ); // signer, nonce, deadline, payload, signature, f
+    assert_eq!(contract.balance_of(1, 2), 1);
+    assert_eq!(contract.nonce(1), 0);
+}
+
+#[test]
+fn execute_signed_failing_action_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(42, 1)]))]),
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let payload = b"transfer 1 of token 2 from 42 to 1";
+    let message = contract.signed_message(1, 0, 200, payload); // signer, nonce, deadline, payload
+    let signature = MockConfig::sign(1, &message);
+    assert_eq!(
+        contract.execute_signed(1, 0, 200, payload, signature, |contract| {
+            contract.safe_transfer_from(42, 1, 2, 1) // from, to, token, amount
+        }),
+        Err(Error::NeedsApproval)
+    ); // signer, nonce, deadline, payload, signature, f
+    assert_eq!(contract.sender(), 9);
+    assert_eq!(contract.nonce(1), 0);
+}
+
+#[test]
+fn buy_not_for_sale_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            value: 35,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.buy(2, 3), Err(Error::NotForSale)); // token, amount
+}
+
+#[test]
+fn buy_with_insufficient_value_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        prices: BTreeMap::from([(2, 10)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            value: 29,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.buy(2, 3), Err(Error::InsufficientValue)); // token, amount
+    assert_eq!(contract.balance_of(1, 2), 0);
+    assert_eq!(contract.proceeds(), 0);
+}
+
+#[test]
+fn buy_with_overflowing_cost_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        prices: BTreeMap::from([(2, u32::MAX)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            value: u32::MAX,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.buy(2, 2), Err(Error::InsufficientValue)); // token, amount
+}
+
+#[test]
+fn buy_beyond_max_supply_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        prices: BTreeMap::from([(2, 10)]),
+        mint_policies: BTreeMap::from([(2, MintPolicy::Capped { max_supply: 2 })]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            value: 30,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.buy(2, 3), Err(Error::MaxSupplyExceeded)); // token, amount
+}
+
+#[test]
+fn set_price_from_non_owner_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.set_price(2, Some(10)),
+        Err(Error::NotContractOwner)
+    ); // token, price
+}
+
+#[test]
+fn withdraw_from_non_owner_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        proceeds: 40,
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.withdraw(), Err(Error::NotContractOwner));
+    assert_eq!(contract.proceeds(), 40);
+}
+
+#[test]
+fn list_with_insufficient_balance_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 2)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.list(2, 3, 10), Err(Error::InsufficientBalance)); // token, amount, price
+    assert_eq!(contract.list(2, 1, 0), Err(Error::ZeroAmount)); // token, amount, price
+    assert_eq!(contract.balance_of(1, 2), 2);
+}
+
+#[test]
+fn cancel_from_non_seller_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    contract.ctx.sender = 7;
+    assert_eq!(contract.cancel(listing_id), Err(Error::NotAccountOwner));
+    assert_eq!(contract.cancel(listing_id + 1), Err(Error::NoSuchListing));
+    assert_eq!(contract.balance_of(200, 2), 3);
+}
+
+#[test]
+fn buy_listing_beyond_listed_amount_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    contract.ctx.sender = 7;
+    contract.ctx.value = 100;
+    assert_eq!(
+        contract.buy_listing(listing_id, 4),
+        Err(Error::InsufficientBalance)
+    ); // listing_id, amount
+    assert_eq!(contract.balance_of(7, 2), 0);
+}
+
+#[test]
+fn buy_listing_with_insufficient_value_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    contract.ctx.sender = 7;
+    contract.ctx.value = 29;
+    assert_eq!(
+        contract.buy_listing(listing_id, 3),
+        Err(Error::InsufficientValue)
+    ); // listing_id, amount
+    assert_eq!(contract.listing(listing_id).map(|l| l.amount), Some(3));
+}
+
+#[test]
+fn buy_own_listing_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            value: 100,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    assert_eq!(
+        contract.buy_listing(listing_id, 1),
+        Err(Error::SelfTransfer)
+    ); // listing_id, amount
+}
+
+#[test]
+fn move_escrowed_units_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 42,
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    for sender in [7, 42, 200] {
+        contract.ctx.sender = sender;
+        assert_eq!(contract.burn(200, 2, 1), Err(Error::EscrowBalance)); // from, token, amount
+        assert_eq!(
+            contract.safe_transfer_from(200, 7, 2, 1),
+            Err(Error::EscrowBalance)
+        ); // from, to, token, amount
+    }
+    assert_eq!(contract.balance_of(200, 2), 3);
+    assert_eq!(contract.listing(listing_id).map(|l| l.amount), Some(3));
+}
+
+#[test]
+fn set_default_royalty_from_non_owner_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.set_default_royalty(Some(Royalty {
+            receiver: 42,
+            basis_points: 250,
+        })),
+        Err(Error::NotContractOwner)
+    );
+    assert_eq!(
+        contract.set_token_royalty(
+            2,
+            Some(Royalty {
+                receiver: 42,
+                basis_points: 250,
+            })
+        ),
+        Err(Error::MissingRole(Role::MetadataAdmin))
+    ); // token, royalty
+}
+
+#[test]
+fn set_royalty_too_high_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 1)]))]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.set_default_royalty(Some(Royalty {
+            receiver: 9,
+            basis_points: 10_001,
+        })),
+        Err(Error::RoyaltyTooHigh)
+    );
+    assert_eq!(
+        contract.set_token_royalty(
+            2,
+            Some(Royalty {
+                receiver: 0,
+                basis_points: 250,
+            })
+        ),
+        Err(Error::ZeroAddress)
+    ); // token, royalty
+    assert_eq!(contract.default_royalty(), None);
+}
+
+#[test]
+fn set_royalty_of_missing_token_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+
+    assert_eq!(
+        contract.set_token_royalty(
+            2,
+            Some(Royalty {
+                receiver: 7,
+                basis_points: 250,
+            })
+        ),
+        Err(Error::NoSuchToken)
+    ); // token, royalty
+    assert_eq!(contract.token_royalty(2), None);
+}
+
+#[test]
+fn transfer_locked_balance_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            block_timestamp: 120,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.mint_vested(1, 2, 100, 100, 10, 50).unwrap(); // to, token, amount, start, cliff, duration
+    contract.ctx.sender = 1;
+    assert_eq!(
+        contract.safe_transfer_from(1, 7, 2, 1),
+        Err(Error::BalanceLocked)
+    ); // from, to, token, amount
+    assert_eq!(contract.burn(1, 2, 1), Err(Error::BalanceLocked)); // from, token, amount
+    contract.release(2).unwrap(); // token
+    assert_eq!(
+        contract.safe_transfer_from(1, 7, 2, 41),
+        Err(Error::BalanceLocked)
+    ); // from, to, token, amount
+}
+
+#[test]
+fn mint_vested_with_invalid_schedule_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+
+    assert_eq!(
+        contract.mint_vested(1, 2, 100, 100, 60, 50),
+        Err(Error::InvalidSchedule)
+    ); // to, token, amount, start, cliff, duration
+    assert_eq!(contract.balance_of(1, 2), 0);
+}
+
+#[test]
+fn release_before_cliff_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            block_timestamp: 100,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.mint_vested(1, 2, 100, 100, 10, 50).unwrap(); // to, token, amount, start, cliff, duration
+    contract.ctx.sender = 1;
+    assert_eq!(contract.release(2), Err(Error::ZeroAmount)); // token
+}
+
+#[test]
+fn mint_overflowing_balance_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        ..Default::default()
+    };
+
+    contract.mint(1, 2, u32::MAX - 1).unwrap(); // to, token, amount
+    assert_eq!(contract.mint(7, 2, 2), Err(Error::BalanceOverflow)); // to, token, amount
+    assert_eq!(contract.balance_of(7, 2), 0);
+    assert_eq!(contract.total_supply(2), u32::MAX - 1);
+}
+
+#[test]
+fn mint_batch_overflowing_balance_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.mint_batch(1, vec![2, 2], vec![u32::MAX, 1]),
+        Err(Error::BalanceOverflow)
+    ); // to, token, amount
+    assert_eq!(contract.total_supply(2), 0);
+}
+
+#[test]
+fn transfer_overflowing_balance_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5), (42, u32::MAX)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.safe_transfer_from(1, 42, 2, 1),
+        Err(Error::BalanceOverflow)
+    ); // from, to, token, amount
+    assert_eq!(contract.balance_of(1, 2), 5);
+    assert_eq!(contract.balance_of(42, 2), u32::MAX);
+}
+
+#[test]
+fn buy_overflowing_proceeds_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        prices: BTreeMap::from([(2, 10)]),
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        proceeds: u32::MAX - 5,
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            value: 10,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.buy(2, 1), Err(Error::BalanceOverflow)); // token, amount
+    assert_eq!(contract.balance_of(1, 2), 0);
+}
+
+#[test]
+fn import_snapshot_when_not_paused_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    let entries = vec![SnapshotEntry::Balance {
+        token: 2,
+        owner: 1,
+        amount: 10,
+    }];
+
+    assert_eq!(contract.import_snapshot(entries), Err(Error::NotPaused));
+    assert_eq!(contract.import_checksum(), [0; 32]);
+}
+
+#[test]
+fn import_invalid_snapshot_entries_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        paused: true,
+        ctx: MockConfig {
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let listing = Listing {
+        seller: 1,
+        token: 2,
+        amount: 3,
+        price: 10,
+    };
+    let schedule = VestingSchedule {
+        token: 2,
+        amount: 6,
+        released: 0,
+        start: 100,
+        cliff: 60,
+        duration: 50,
+    };
+
+    assert_eq!(
+        contract.import_snapshot(vec![SnapshotEntry::Balance {
+            token: 2,
+            owner: 200,
+            amount: 10,
+        }]),
+        Err(Error::EscrowBalance)
+    );
+    assert_eq!(
+        contract.import_snapshot(vec![
+            SnapshotEntry::Listing {
+                listing_id: 0,
+                listing: listing.clone(),
+            },
+            SnapshotEntry::Listing {
+                listing_id: 0,
+                listing,
+            },
+        ]),
+        Err(Error::AlreadyMinted)
+    );
+    assert_eq!(
+        contract.import_snapshot(vec![SnapshotEntry::VestingSchedule { owner: 7, schedule }]),
+        Err(Error::InvalidSchedule)
+    );
+    assert_eq!(
+        contract.import_snapshot(vec![SnapshotEntry::Metadata {
+            token: 2,
+            metadata: Box::new(TokenMetadata {
+                name: "a".repeat(129),
+                ..Default::default()
+            }),
+        }]),
+        Err(Error::InvalidMetadata)
+    );
+    assert_eq!(
+        contract.import_snapshot(vec![SnapshotEntry::TokenRoyalty {
+            token: 2,
+            royalty: Royalty {
+                receiver: 7,
+                basis_points: 10_001,
+            },
+        }]),
+        Err(Error::RoyaltyTooHigh)
+    );
+    assert_eq!(contract.get_token_metadata(2), None);
+    assert_eq!(contract.import_checksum(), [0; 32]);
+    assert_eq!(contract.total_supply(2), 0);
+}
+
+#[test]
+fn import_snapshot_twice_fails() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        paused: true,
+        ..Default::default()
+    };
+    let entries = vec![SnapshotEntry::Balance {
+        token: 2,
+        owner: 1,
+        amount: 10,
+    }];
+
+    let checksum = contract.import_snapshot(entries.clone()).unwrap();
+    assert_eq!(contract.import_snapshot(entries), Err(Error::AlreadyMinted));
+    assert_eq!(contract.import_checksum(), checksum);
+    assert_eq!(contract.total_supply(2), 10);
 }
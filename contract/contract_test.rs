@@ -2,12 +2,13 @@
 
 use crate::*;
 use config::*;
+use proptest::prelude::*;
 
 #[test]
 fn mint_works() {
     let expected = BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]);
     let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
-    contract.mint(1, 2, 3); // to, token, amount
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -19,7 +20,9 @@ fn mint_batch_works() {
         (2, BTreeMap::from([(1, 3)])),
     ]);
     let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
-    contract.mint_batch(1, vec![0, 1, 2], vec![1, 2, 3]); // to, token, amount
+    contract
+        .mint_batch(1, vec![0, 1, 2], vec![1, 2, 3])
+        .unwrap(); // to, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -54,7 +57,7 @@ fn balance_of_batch_works() {
 
     assert_eq!(
         contract.balance_of_batch(vec![1, 1, 1, 1], vec![0, 1, 2, 3]),
-        expected
+        Ok(expected)
     ); // who, token
 }
 
@@ -66,11 +69,12 @@ fn transfer_works() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_transfer_from(1, 42, 0, 1); // from, to, token, amount
+    contract.safe_transfer_from(1, 42, 0, 1).unwrap(); // from, to, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -83,11 +87,12 @@ fn transfer_from_approved_works() {
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_transfer_from(1, 42, 2, 1); // from, to, token, amount
+    contract.safe_transfer_from(1, 42, 2, 1).unwrap(); // from, to, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -107,11 +112,14 @@ fn transfer_batch_works() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![3, 2, 1]); // from, to, token, amount
+    contract
+        .safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![3, 2, 1])
+        .unwrap(); // from, to, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -132,11 +140,14 @@ fn transfer_batch_from_approved_works() {
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![3, 2, 1]); // from, to, token, amount
+    contract
+        .safe_batch_transfer_from(1, 42, vec![0, 1, 2], vec![3, 2, 1])
+        .unwrap(); // from, to, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -148,11 +159,12 @@ fn burn_works() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn(1, 2, 3); // from, token, amount
+    contract.burn(1, 2, 3).unwrap(); // from, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -165,11 +177,12 @@ fn burn_from_approved_works() {
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn(1, 2, 3); // from, token, amount
+    contract.burn(1, 2, 3).unwrap(); // from, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -190,11 +203,14 @@ fn burn_batch_works() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn_batch(1, vec![0, 1, 2], vec![3, 3, 3]); // from, token, amount
+    contract
+        .burn_batch(1, vec![0, 1, 2], vec![3, 3, 3])
+        .unwrap(); // from, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -215,11 +231,14 @@ fn burn_batch_from_approved_works() {
         ctx: MockConfig {
             sender: 42,
             origin: 42,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.burn_batch(1, vec![0, 1, 2], vec![4, 4, 4]); // from, token, amount
+    contract
+        .burn_batch(1, vec![0, 1, 2], vec![4, 4, 4])
+        .unwrap(); // from, token, amount
     assert_eq!(contract.balances, expected);
 }
 
@@ -248,12 +267,13 @@ fn set_approval_for_all_from_sender_works() {
         ctx: MockConfig {
             sender: 1,
             origin: 0,
+            ..Default::default()
         },
         approvals: BTreeMap::from([(1, BTreeMap::from([(42, true)]))]),
         ..Default::default()
     };
 
-    contract.set_approval_for_all(1, 42, false); // owner, operator
+    contract.set_approval_for_all(1, 42, false).unwrap(); // owner, operator
     assert_eq!(contract.approvals, expected);
 }
 
@@ -264,12 +284,13 @@ fn set_approval_for_all_works() {
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         approvals: BTreeMap::from([(1, BTreeMap::from([(42, true)]))]),
         ..Default::default()
     };
 
-    contract.set_approval_for_all(1, 42, false); // owner, operator
+    contract.set_approval_for_all(1, 42, false).unwrap(); // owner, operator
     assert_eq!(contract.approvals, expected);
 }
 
@@ -287,23 +308,60 @@ fn update_token_metadata_works() {
         description: "nft for test".to_string(),
         image_uri: "https://gm.dev/nft.png".to_string(),
         json_uri: "https://gm.dev/nft.json".to_string(),
+        ..Default::default()
     });
 
     let expected = BTreeMap::from([(2, some_metadata.clone().unwrap())]);
 
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         ..Default::default()
     };
 
-    contract.update_token_metadata(2, some_metadata);
+    contract.update_token_metadata(2, some_metadata).unwrap();
     assert_eq!(contract.metadata_registry, expected);
 }
 
+#[test]
+fn update_rich_token_metadata_works() {
+    let json = r#"{"name":"sword"}"#;
+    let metadata = TokenMetadata {
+        name: "sword".to_string(),
+        json_uri: "https://gm.dev/sword.json".to_string(),
+        attributes: vec![
+            ("rarity".to_string(), "epic".to_string()),
+            ("damage".to_string(), "42".to_string()),
+        ],
+        animation_url: "https://gm.dev/sword.mp4".to_string(),
+        external_url: "https://gm.dev/sword".to_string(),
+        decimals: 18,
+        content_hash: Some(crate::permit::blake2_256(json.as_bytes())),
+        ..Default::default()
+    };
+
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract
+        .update_token_metadata(2, Some(metadata.clone()))
+        .unwrap();
+    assert_eq!(contract.get_token_metadata(2), Some(metadata));
+}
+
 #[test]
 fn remove_update_token_metadata_works() {
     let some_metadata = Some(TokenMetadata {
@@ -311,20 +369,1126 @@ fn remove_update_token_metadata_works() {
         description: "nft for test".to_string(),
         image_uri: "https://gm.dev/nft.png".to_string(),
         json_uri: "https://gm.dev/nft.json".to_string(),
+        ..Default::default()
     });
 
     let expected = BTreeMap::from([]);
 
     let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
         balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
         ctx: MockConfig {
             sender: 1,
             origin: 1,
+            ..Default::default()
         },
         metadata_registry: BTreeMap::from([(2, some_metadata.unwrap())]),
         ..Default::default()
     };
 
-    contract.update_token_metadata(2, None);
+    contract.update_token_metadata(2, None).unwrap();
+    assert_eq!(contract.metadata_registry, expected);
+}
+
+#[test]
+fn uri_substitutes_token_id() {
+    let contract: Contract<MockConfig> = Contract::<MockConfig> {
+        base_uri: "https://gm.dev/{id}.json".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.uri(0x2a),
+        "https://gm.dev/000000000000000000000000000000000000000000000000000000000000002a.json"
+    );
+}
+
+#[test]
+fn uri_substitutes_legacy_placeholder() {
+    let contract: Contract<MockConfig> = Contract::<MockConfig> {
+        base_uri: "https://gm.dev/{}".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        contract.uri(0x2a),
+        "https://gm.dev/000000000000000000000000000000000000000000000000000000000000002a"
+    );
+}
+
+#[test]
+fn set_token_uri_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        base_uri: "https://gm.dev/{id}.json".to_string(),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract
+        .set_token_uri(2, Some("ipfs://nft/{id}".to_string()))
+        .unwrap();
+    assert_eq!(
+        contract.uri(2),
+        "ipfs://nft/0000000000000000000000000000000000000000000000000000000000000002"
+    );
+
+    contract.set_token_uri(2, None).unwrap();
+    assert_eq!(
+        contract.uri(2),
+        "https://gm.dev/0000000000000000000000000000000000000000000000000000000000000002.json"
+    );
+}
+
+#[test]
+fn total_supply_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.mint_batch(1, vec![0, 1], vec![5, 1]).unwrap(); // to, token, amount
+    assert_eq!(contract.total_supply(0), 5);
+    assert_eq!(contract.total_supply(1), 1);
+    assert_eq!(contract.total_supply(2), 0);
+
+    contract.ctx.set_sender(1);
+    contract.safe_transfer_from(1, 42, 0, 2).unwrap(); // from, to, token, amount
+    assert_eq!(contract.total_supply(0), 5);
+
+    contract.burn(1, 0, 3).unwrap(); // from, token, amount
+    contract.burn(1, 1, 1).unwrap(); // from, token, amount
+    assert_eq!(contract.total_supply(0), 2);
+    assert_eq!(contract.total_supply(1), 0);
+}
+
+#[test]
+fn exists_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    assert!(!contract.exists(2));
+
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    assert!(contract.exists(2));
+
+    contract.ctx.set_sender(1);
+    contract.burn(1, 2, 3).unwrap(); // from, token, amount
+    assert!(!contract.exists(2));
+}
+
+#[test]
+fn owner_has_all_roles() {
+    let contract: Contract<MockConfig> = Contract::<MockConfig>::new(&1);
+    assert!(contract.has_role(1, Role::Minter));
+    assert!(contract.has_role(1, Role::MetadataAdmin));
+    assert!(contract.has_role(1, Role::Pauser));
+    assert!(!contract.has_role(42, Role::Minter));
+}
+
+#[test]
+fn grant_role_works() {
+    let expected = BTreeMap::from([(42, BTreeSet::from([Role::Minter]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.grant_role(42, Role::Minter).unwrap(); // account, role
+    assert_eq!(contract.roles, expected);
+    assert!(contract.has_role(42, Role::Minter));
+    assert!(!contract.has_role(42, Role::Pauser));
+}
+
+#[test]
+fn revoke_role_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::Minter, Role::Pauser]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.revoke_role(42, Role::Minter).unwrap(); // account, role
+    assert_eq!(
+        contract.roles,
+        BTreeMap::from([(42, BTreeSet::from([Role::Pauser]))])
+    );
+
+    contract.revoke_role(42, Role::Pauser).unwrap(); // account, role
+    assert_eq!(contract.roles, BTreeMap::from([]));
+}
+
+#[test]
+fn renounce_role_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::Minter]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.renounce_role(42, Role::Minter).unwrap(); // account, role
+    assert!(!contract.has_role(42, Role::Minter));
+}
+
+#[test]
+fn transfer_ownership_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.transfer_ownership(42).unwrap();
+    assert_eq!(contract.owner(), 42);
+    assert!(!contract.has_role(1, Role::Minter));
+    assert!(contract.has_role(42, Role::Minter));
+}
+
+#[test]
+fn mint_from_minter_works() {
+    let expected = BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::Minter]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    assert_eq!(contract.balances, expected);
+}
+
+#[test]
+fn update_token_metadata_from_metadata_admin_works() {
+    let some_metadata = Some(TokenMetadata {
+        name: "nft".to_string(),
+        description: "nft for test".to_string(),
+        image_uri: "https://gm.dev/nft.png".to_string(),
+        json_uri: "https://gm.dev/nft.json".to_string(),
+        ..Default::default()
+    });
+
+    let expected = BTreeMap::from([(2, some_metadata.clone().unwrap())]);
+
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::MetadataAdmin]))]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.update_token_metadata(2, some_metadata).unwrap();
     assert_eq!(contract.metadata_registry, expected);
 }
+
+#[test]
+fn first_minter_is_creator() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        roles: BTreeMap::from([(7, BTreeSet::from([Role::Minter]))]),
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        ..Default::default()
+    };
+    assert_eq!(contract.creator(2), None);
+
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    contract.ctx.sender = 7;
+    contract.mint(7, 2, 3).unwrap(); // to, token, amount
+    contract.mint_batch(7, vec![3], vec![1]).unwrap(); // to, token, amount
+    assert_eq!(contract.creator(2), Some(0));
+    assert_eq!(contract.creator(3), Some(7));
+}
+
+#[test]
+fn freeze_metadata_works() {
+    let metadata = TokenMetadata {
+        name: "nft".to_string(),
+        ..Default::default()
+    };
+
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        creators: BTreeMap::from([(2, 1)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract
+        .update_token_metadata(2, Some(metadata.clone()))
+        .unwrap();
+    assert!(!contract.is_metadata_frozen(2));
+    contract.freeze_metadata(2).unwrap();
+    assert!(contract.is_metadata_frozen(2));
+    assert!(!contract.is_metadata_frozen(3));
+    assert_eq!(contract.get_token_metadata(2), Some(metadata));
+}
+
+#[test]
+fn default_mint_policy_is_fixed() {
+    let contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    assert_eq!(contract.mint_policy(2), MintPolicy::Fixed);
+}
+
+#[test]
+fn mint_unlimited_token_twice_works() {
+    let expected = BTreeMap::from([(2, BTreeMap::from([(1, 6)]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.create_token(2, MintPolicy::Unlimited).unwrap(); // token, policy
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    assert_eq!(contract.balances, expected);
+    assert_eq!(contract.total_supply(2), 6);
+}
+
+#[test]
+fn mint_capped_token_up_to_max_supply_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract
+        .create_token(2, MintPolicy::Capped { max_supply: 5 })
+        .unwrap(); // token, policy
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    contract.mint(42, 2, 2).unwrap(); // to, token, amount
+    assert_eq!(contract.total_supply(2), 5);
+}
+
+#[test]
+fn register_receiver_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 100,
+            origin: 100,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(!contract.is_receiver(100));
+    contract.register_receiver(100).unwrap();
+    assert!(contract.is_receiver(100));
+    contract.unregister_receiver(100).unwrap();
+    assert!(!contract.is_receiver(100));
+}
+
+#[test]
+fn approve_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.approve(1, 42, 2, 3).unwrap(); // owner, operator, token, amount
+    assert_eq!(contract.allowance(1, 42, 2), 3);
+    contract.approve(1, 42, 2, 0).unwrap(); // owner, operator, token, amount
+    assert_eq!(contract.allowance(1, 42, 2), 0);
+    assert!(contract.allowances.is_empty());
+}
+
+#[test]
+fn transfer_from_allowance_works() {
+    let expected = BTreeMap::from([(2, BTreeMap::from([(1, 1), (42, 2)]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        allowances: BTreeMap::from([(1, BTreeMap::from([((42, 2), 3)]))]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.safe_transfer_from(1, 42, 2, 2).unwrap(); // from, to, token, amount
+    assert_eq!(contract.balances, expected);
+    assert_eq!(contract.allowance(1, 42, 2), 1);
+}
+
+#[test]
+fn burn_from_allowance_works() {
+    let expected = BTreeMap::from([(2, BTreeMap::from([(1, 1)]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        allowances: BTreeMap::from([(1, BTreeMap::from([((42, 2), 2)]))]),
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 3)]))]),
+        total_supply: BTreeMap::from([(2, 3)]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.burn(1, 2, 2).unwrap(); // from, token, amount
+    assert_eq!(contract.balances, expected);
+    assert_eq!(contract.allowance(1, 42, 2), 0);
+}
+
+#[test]
+fn subscribe_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+
+    assert!(!contract.is_subscriber(7));
+    contract.subscribe(7).unwrap();
+    contract.subscribe(8).unwrap();
+    assert!(contract.is_subscriber(7));
+
+    contract.ctx.sender = 7;
+    contract.unsubscribe(7).unwrap();
+    assert!(!contract.is_subscriber(7));
+
+    contract.ctx.sender = 0;
+    contract.unsubscribe(8).unwrap();
+    assert!(contract.subscribers.is_empty());
+}
+
+#[test]
+fn tokens_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.mint(1, 0, 1).unwrap(); // to, token, amount
+    contract.mint(1, 1, 1).unwrap(); // to, token, amount
+    contract.mint(1, 2, 1).unwrap(); // to, token, amount
+    assert_eq!(contract.tokens(0, 10), vec![0, 1, 2]); // offset, limit
+    assert_eq!(contract.tokens(1, 1), vec![1]); // offset, limit
+    assert_eq!(contract.tokens(3, 10), vec![]); // offset, limit
+}
+
+#[test]
+fn holders_of_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.create_token(2, MintPolicy::Unlimited).unwrap(); // token, policy
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+    contract.mint(42, 2, 4).unwrap(); // to, token, amount
+    assert_eq!(contract.holders_of(2, 0, 10), vec![(1, 3), (42, 4)]); // token, offset, limit
+    assert_eq!(contract.holders_of(2, 1, 10), vec![(42, 4)]); // token, offset, limit
+    assert_eq!(contract.holders_of(3, 0, 10), vec![]); // token, offset, limit
+}
+
+#[test]
+fn tokens_of_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        owner: 1,
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract
+        .mint_batch(1, vec![0, 1, 2], vec![1, 2, 3])
+        .unwrap(); // to, token, amount
+    contract.safe_transfer_from(1, 42, 1, 2).unwrap(); // from, to, token, amount
+    contract.burn(1, 2, 1).unwrap(); // from, token, amount
+    assert_eq!(contract.tokens_of(1, 0, 10), vec![(0, 1), (2, 2)]); // owner, offset, limit
+    assert_eq!(contract.tokens_of(1, 0, 1), vec![(0, 1)]); // owner, offset, limit
+    assert_eq!(contract.tokens_of(42, 0, 10), vec![(1, 2)]); // owner, offset, limit
+    contract.burn(1, 0, 1).unwrap(); // from, token, amount
+    contract.burn(1, 2, 2).unwrap(); // from, token, amount
+    assert_eq!(contract.tokens_of(1, 0, 10), vec![]); // owner, offset, limit
+    assert!(!contract.owned_tokens.contains_key(&1));
+    assert_eq!(contract.tokens(0, 10), vec![1]); // offset, limit
+}
+
+#[test]
+fn pause_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.pause().unwrap();
+    assert!(contract.is_paused());
+    contract.unpause().unwrap();
+    assert!(!contract.is_paused());
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+}
+
+#[test]
+fn pause_token_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        roles: BTreeMap::from([(42, BTreeSet::from([Role::Pauser]))]),
+        ctx: MockConfig {
+            sender: 42,
+            origin: 42,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.pause_token(2).unwrap(); // token
+    assert!(contract.is_token_paused(2));
+    assert!(!contract.is_token_paused(3));
+    contract.unpause_token(2).unwrap(); // token
+    assert!(!contract.is_token_paused(2));
+}
+
+#[test]
+fn transfer_batch_with_duplicate_ids_works() {
+    let expected = BTreeMap::from([(2, BTreeMap::from([(1, 1), (42, 4)]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract
+        .safe_batch_transfer_from(1, 42, vec![2, 2], vec![3, 1])
+        .unwrap(); // from, to, token, amount
+    assert_eq!(contract.balances, expected);
+}
+
+/// batch of up to 8 (token, amount) pairs over 3 token ids, so that
+/// duplicate ids are common
+fn batch() -> impl Strategy<Value = (Vec<u8>, Vec<u32>)> {
+    proptest::collection::vec((0u8..3, 1u32..8), 1..8).prop_map(|ops| ops.into_iter().unzip())
+}
+
+fn totals(token: &[u8], amount: &[u32]) -> BTreeMap<u8, u32> {
+    let mut totals = BTreeMap::new();
+    for (tk, am) in token.iter().zip(amount) {
+        *totals.entry(*tk).or_insert(0) += am;
+    }
+    totals
+}
+
+proptest! {
+    #[test]
+    fn transfer_batch_is_all_or_nothing((token, amount) in batch()) {
+        let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+            owner: 1,
+            ctx: MockConfig {
+                sender: 1,
+                origin: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        contract.mint_batch(1, vec![0, 1, 2], vec![10, 10, 10]).unwrap(); // to, token, amount
+        let before = contract.clone();
+        let totals = totals(&token, &amount);
+
+        match contract.safe_batch_transfer_from(1, 42, token, amount) {
+            Ok(()) => {
+                for (tk, total) in totals {
+                    prop_assert!(total <= 10);
+                    prop_assert_eq!(contract.balance_of(1, tk), 10 - total);
+                    prop_assert_eq!(contract.balance_of(42, tk), total);
+                }
+            }
+            Err(error) => {
+                prop_assert_eq!(error, Error::InsufficientBalance);
+                prop_assert!(totals.values().any(|total| *total > 10));
+                prop_assert_eq!(contract.balances, before.balances);
+                prop_assert_eq!(contract.owned_tokens, before.owned_tokens);
+            }
+        }
+    }
+
+    #[test]
+    fn burn_batch_is_all_or_nothing((token, amount) in batch()) {
+        let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+        contract.mint_batch(1, vec![0, 1, 2], vec![10, 10, 10]).unwrap(); // to, token, amount
+        contract.ctx.set_sender(1);
+        let before = contract.clone();
+        let totals = totals(&token, &amount);
+
+        match contract.burn_batch(1, token, amount) {
+            Ok(()) => {
+                for (tk, total) in totals {
+                    prop_assert!(total <= 10);
+                    prop_assert_eq!(contract.balance_of(1, tk), 10 - total);
+                    prop_assert_eq!(contract.total_supply(tk), 10 - total);
+                }
+            }
+            Err(error) => {
+                prop_assert_eq!(error, Error::InsufficientBalance);
+                prop_assert!(totals.values().any(|total| *total > 10));
+                prop_assert_eq!(contract.balances, before.balances);
+                prop_assert_eq!(contract.total_supply, before.total_supply);
+            }
+        }
+    }
+
+    #[test]
+    fn mint_batch_is_all_or_nothing((token, amount) in batch()) {
+        let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+        for tk in 0..3 {
+            contract
+                .create_token(tk, MintPolicy::Capped { max_supply: 10 })
+                .unwrap(); // token, policy
+        }
+        let totals = totals(&token, &amount);
+
+        match contract.mint_batch(1, token, amount) {
+            Ok(()) => {
+                for (tk, total) in totals {
+                    prop_assert!(total <= 10);
+                    prop_assert_eq!(contract.balance_of(1, tk), total);
+                    prop_assert_eq!(contract.total_supply(tk), total);
+                }
+            }
+            Err(error) => {
+                prop_assert_eq!(error, Error::MaxSupplyExceeded);
+                prop_assert!(totals.values().any(|total| *total > 10));
+                prop_assert!(contract.balances.is_empty());
+                prop_assert!(contract.total_supply.is_empty());
+            }
+        }
+    }
+}
+
+#[test]
+fn permit_for_all_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            block_timestamp: 100,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let message = contract.permit_message(1, 42, true, 200, 0); // owner, operator, approved, deadline, nonce
+    let signature = MockConfig::sign(1, &message);
+    contract
+        .permit_for_all(1, 42, true, 200, 0, signature)
+        .unwrap(); // owner, operator, approved, deadline, nonce, signature
+    assert!(contract.is_approved_for_all(1, 42));
+    assert_eq!(contract.nonce(1), 1);
+
+    let message = contract.permit_message(1, 42, false, 200, 1); // owner, operator, approved, deadline, nonce
+    let signature = MockConfig::sign(1, &message);
+    contract
+        .permit_for_all(1, 42, false, 200, 1, signature)
+        .unwrap(); // owner, operator, approved, deadline, nonce, signature
+    assert!(!contract.is_approved_for_all(1, 42));
+    assert_eq!(contract.nonce(1), 2);
+}
+
+#[test]
+fn domain_separator_depends_on_program_id() {
+    let contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            program_id: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let other: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            program_id: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_ne!(contract.domain_separator(), other.domain_separator());
+    assert_ne!(
+        contract.permit_message(1, 42, true, 200, 0),
+        other.permit_message(1, 42, true, 200, 0)
+    ); // owner, operator, approved, deadline, nonce
+}
+
+#[test]
+fn signed_message_domain_differs_from_permit() {
+    let contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    let payload = [42];
+    let under_permit_domain = crate::permit::blake2_256(
+        &(contract.domain_separator(), 1u8, 0u64, 200u64, &payload[..]).encode(),
+    );
+
+    assert_ne!(
+        contract.signed_message(1, 0, 200, &payload),
+        under_permit_domain
+    ); // signer, nonce, deadline, payload
+}
+
+#[test]
+fn execute_signed_works() {
+    let expected = BTreeMap::from([(2, BTreeMap::from([(1, 0), (42, 1)]))]);
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 1)]))]),
+        ctx: MockConfig {
+            sender: 9,
+            origin: 9,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let payload = b"transfer 1 of token 2 to 42";
+    let message = contract.signed_message(1, 0, 200, payload); // signer, nonce, deadline, payload
+    let signature = MockConfig::sign(1, &message);
+    contract
+        .execute_signed(1, 0, 200, payload, signature, |contract| {
+            assert_eq!(contract.sender(), 1);
+            contract.safe_transfer_from(1, 42, 2, 1) // from, to, token, amount
+        })
+        .unwrap(); // signer, nonce, deadline, payload, signature, f
+    assert_eq!(contract.balances, expected);
+    assert_eq!(contract.sender(), 9);
+    assert_eq!(contract.nonce(1), 1);
+}
+
+#[test]
+fn set_price_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    assert_eq!(contract.price(2), None);
+    contract.set_price(2, Some(10)).unwrap(); // token, price
+    assert_eq!(contract.price(2), Some(10));
+    contract.set_price(2, None).unwrap(); // token, price
+    assert_eq!(contract.price(2), None);
+}
+
+#[test]
+fn buy_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        prices: BTreeMap::from([(2, 10)]),
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            value: 35,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.buy(2, 3), Ok((30, 5))); // token, amount
+    assert_eq!(contract.balance_of(1, 2), 3);
+    assert_eq!(contract.total_supply(2), 3);
+    assert_eq!(contract.proceeds(), 30);
+    assert_eq!(contract.buy(2, 1), Ok((10, 25))); // token, amount
+    assert_eq!(contract.proceeds(), 40);
+}
+
+#[test]
+fn withdraw_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        proceeds: 40,
+        ..Default::default()
+    };
+
+    assert_eq!(contract.withdraw(), Ok(40));
+    assert_eq!(contract.proceeds(), 0);
+}
+
+#[test]
+fn list_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(contract.list(2, 3, 10), Ok(0)); // token, amount, price
+    assert_eq!(contract.list(2, 1, 20), Ok(1)); // token, amount, price
+    assert_eq!(contract.balance_of(1, 2), 1);
+    assert_eq!(contract.balance_of(200, 2), 4);
+    assert_eq!(
+        contract.listings(2, 0, 10), // token, offset, limit
+        vec![
+            (
+                0,
+                Listing {
+                    seller: 1,
+                    token: 2,
+                    amount: 3,
+                    price: 10
+                }
+            ),
+            (
+                1,
+                Listing {
+                    seller: 1,
+                    token: 2,
+                    amount: 1,
+                    price: 20
+                }
+            ),
+        ]
+    );
+    assert_eq!(contract.listings(2, 1, 10).len(), 1); // token, offset, limit
+    assert!(contract.listings(3, 0, 10).is_empty()); // token, offset, limit
+}
+
+#[test]
+fn cancel_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    assert_eq!(contract.cancel(listing_id), Ok(()));
+    assert_eq!(contract.balance_of(1, 2), 5);
+    assert_eq!(contract.balance_of(200, 2), 0);
+    assert_eq!(contract.listing(listing_id), None);
+}
+
+#[test]
+fn buy_listing_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        balances: BTreeMap::from([(2, BTreeMap::from([(1, 5)]))]),
+        ctx: MockConfig {
+            sender: 1,
+            origin: 1,
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let listing_id = contract.list(2, 3, 10).unwrap(); // token, amount, price
+    contract.ctx.sender = 7;
+    contract.ctx.value = 25;
+
+    assert_eq!(contract.buy_listing(listing_id, 2), Ok((1, 20, 5))); // listing_id, amount
+    assert_eq!(contract.balance_of(7, 2), 2);
+    assert_eq!(contract.balance_of(200, 2), 1);
+    assert_eq!(contract.listing(listing_id).map(|l| l.amount), Some(1));
+    assert_eq!(contract.buy_listing(listing_id, 1), Ok((1, 10, 15))); // listing_id, amount
+    assert_eq!(contract.balance_of(7, 2), 3);
+    assert_eq!(contract.balance_of(200, 2), 0);
+    assert_eq!(contract.listing(listing_id), None);
+    assert_eq!(contract.tokens_of(200, 0, 10), vec![]); // owner, offset, limit
+}
+
+#[test]
+fn royalty_info_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.mint(1, 2, 1).unwrap(); // to, token, amount
+    assert_eq!(contract.royalty_info(2, 1000), (0, 0)); // token, sale_price
+
+    contract
+        .set_default_royalty(Some(Royalty {
+            receiver: 9,
+            basis_points: 250,
+        }))
+        .unwrap();
+    contract
+        .set_token_royalty(
+            2,
+            Some(Royalty {
+                receiver: 7,
+                basis_points: 1000,
+            }),
+        )
+        .unwrap(); // token, royalty
+    assert_eq!(contract.royalty_info(1, 1000), (9, 25)); // token, sale_price
+    assert_eq!(contract.royalty_info(2, 1000), (7, 100)); // token, sale_price
+    assert_eq!(contract.royalty_info(2, u32::MAX), (7, 429_496_729)); // token, sale_price
+
+    contract.set_token_royalty(2, None).unwrap(); // token, royalty
+    assert_eq!(contract.royalty_info(2, 1000), (9, 25)); // token, sale_price
+}
+
+#[test]
+fn release_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        ctx: MockConfig {
+            block_timestamp: 100,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    contract.mint_vested(1, 2, 100, 100, 10, 50).unwrap(); // to, token, amount, start, cliff, duration
+    assert_eq!(contract.balance_of(1, 2), 100);
+    assert_eq!(contract.locked_balance_of(1, 2), 100);
+
+    contract.ctx.block_timestamp = 109;
+    assert_eq!(contract.releasable(1, 2), 0);
+    contract.ctx.block_timestamp = 120;
+    assert_eq!(contract.releasable(1, 2), 40);
+
+    contract.ctx.sender = 1;
+    assert_eq!(contract.release(2), Ok(40));
+    assert_eq!(contract.locked_balance_of(1, 2), 60);
+    assert_eq!(contract.vesting_schedules(1)[0].released, 40);
+    contract.safe_transfer_from(1, 7, 2, 40).unwrap(); // from, to, token, amount
+
+    contract.ctx.block_timestamp = 150;
+    assert_eq!(contract.release(2), Ok(60));
+    assert_eq!(contract.locked_balance_of(1, 2), 0);
+    assert_eq!(contract.vesting_schedules(1), vec![]);
+}
+
+#[test]
+fn vested_amount_of_long_schedule_works() {
+    const YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
+    let schedule = VestingSchedule {
+        token: 2,
+        amount: u32::MAX,
+        released: 0,
+        start: 0,
+        cliff: 0,
+        duration: 4 * YEAR,
+    };
+
+    assert_eq!(
+        Contract::<MockConfig>::vested_amount(&schedule, YEAR),
+        u32::MAX / 4
+    );
+    assert_eq!(
+        Contract::<MockConfig>::vested_amount(&schedule, 4 * YEAR),
+        u32::MAX
+    );
+}
+
+#[test]
+fn mint_up_to_max_balance_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        ..Default::default()
+    };
+
+    contract.mint(1, 2, u32::MAX - 1).unwrap(); // to, token, amount
+    contract.mint(1, 2, 1).unwrap(); // to, token, amount
+    assert_eq!(contract.balance_of(1, 2), u32::MAX);
+    assert_eq!(contract.total_supply(2), u32::MAX);
+}
+
+#[test]
+fn snapshot_round_trip_works() {
+    let mut source: Contract<MockConfig> = Contract::<MockConfig> {
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        metadata_registry: BTreeMap::from([(1, TokenMetadata::default())]),
+        token_uris: BTreeMap::from([(1, "ipfs://1".to_string())]),
+        roles: BTreeMap::from([(7, BTreeSet::from([Role::Minter, Role::Pauser]))]),
+        default_royalty: Some(Royalty {
+            receiver: 1,
+            basis_points: 250,
+        }),
+        token_royalties: BTreeMap::from([(
+            2,
+            Royalty {
+                receiver: 7,
+                basis_points: 500,
+            },
+        )]),
+        prices: BTreeMap::from([(2, 30)]),
+        ..Default::default()
+    };
+    source.mint(1, 1, 1).unwrap(); // to, token, amount
+    source.mint(1, 2, 10).unwrap(); // to, token, amount
+    source.mint(7, 2, 5).unwrap(); // to, token, amount
+    source.freeze_metadata(1).unwrap();
+    source.ctx.sender = 1;
+    source.set_approval_for_all(1, 42, true).unwrap(); // owner, operator, approved
+    source.approve(1, 7, 2, 3).unwrap(); // owner, operator, token, amount
+
+    let mut target: Contract<MockConfig> = Contract::<MockConfig> {
+        paused: true,
+        ..Default::default()
+    };
+    let mut cursor = Some(0);
+    while let Some(from) = cursor {
+        let (entries, next) = source.snapshot(from, 2); // cursor, limit
+        assert!(entries.len() <= 2);
+        target.import_snapshot(entries).unwrap();
+        cursor = next;
+    }
+
+    assert_eq!(target.import_checksum(), source.snapshot_checksum());
+    assert_eq!(target.snapshot_checksum(), source.snapshot_checksum());
+    assert_eq!(target.balances, source.balances);
+    assert_eq!(target.total_supply, source.total_supply);
+    assert_eq!(target.owned_tokens, source.owned_tokens);
+    assert_eq!(target.allowance(1, 7, 2), 3); // owner, operator, token
+    assert!(target.is_approved_for_all(1, 42));
+    assert_eq!(target.mint_policy(2), MintPolicy::Unlimited);
+    assert_eq!(target.uri(1), "ipfs://1");
+    assert_eq!(target.creators, source.creators);
+    assert!(target.is_metadata_frozen(1));
+    assert_eq!(target.roles, source.roles);
+    assert!(target.has_role(7, Role::Pauser));
+    assert_eq!(target.royalty_info(1, 1000), (1, 25)); // token, sale_price
+    assert_eq!(target.royalty_info(2, 1000), (7, 50)); // token, sale_price
+    assert_eq!(target.price(2), Some(30));
+}
+
+#[test]
+fn snapshot_round_trip_with_listings_works() {
+    let mut source: Contract<MockConfig> = Contract::<MockConfig> {
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        ctx: MockConfig {
+            program_id: 200,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    source.mint(1, 2, 10).unwrap(); // to, token, amount
+    source.mint_vested(7, 2, 6, 100, 10, 50).unwrap(); // to, token, amount, start, cliff, duration
+    source.ctx.sender = 1;
+    source.list(2, 3, 10).unwrap(); // token, amount, price
+    source.list(2, 2, 20).unwrap(); // token, amount, price
+    source.cancel(0).unwrap();
+
+    let mut target: Contract<MockConfig> = Contract::<MockConfig> {
+        paused: true,
+        ctx: MockConfig {
+            program_id: 201,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (entries, next) = source.snapshot(0, 10); // cursor, limit
+    assert_eq!(next, None);
+    target.import_snapshot(entries).unwrap();
+
+    assert_eq!(target.import_checksum(), source.snapshot_checksum());
+    assert_eq!(target.balance_of(1, 2), 8);
+    assert_eq!(target.balance_of(200, 2), 0);
+    assert_eq!(target.balance_of(201, 2), 2);
+    assert_eq!(target.total_supply(2), source.total_supply(2));
+    assert_eq!(target.listings, source.listings);
+    assert_eq!(target.next_listing_id, 2);
+    assert_eq!(target.vesting_schedules(7), source.vesting_schedules(7));
+    assert_eq!(target.locked_balance_of(7, 2), 6); // owner, token
+}
+
+/// `StateV1<MockConfig>` encoded by the first layout, must never change
+const STATE_V1_FIXTURE: [u8; 61] = [
+    1, 8, 103, 109, 8, 71, 77, 68, 104, 116, 116, 112, 115, 58, 47, 47, 103, 109, 46, 100, 101,
+    118, 47, 123, 125, 8, 2, 8, 1, 10, 0, 0, 0, 7, 5, 0, 0, 0, 3, 4, 7, 1, 0, 0, 0, 4, 1, 4, 42, 1,
+    4, 3, 20, 115, 119, 111, 114, 100, 0, 0, 0,
+];
+
+#[test]
+fn migrate_v1_fixture_works() {
+    let v1 = StateV1::<MockConfig>::decode(&mut &STATE_V1_FIXTURE[..]).unwrap();
+    let state = VersionedState::V1(v1);
+    assert_eq!(state.version(), 1);
+
+    let contract = state.into_latest();
+    assert_eq!(contract.version(), STATE_VERSION);
+    assert_eq!(contract.owner(), 1);
+    assert_eq!(contract.name(), "gm");
+    assert_eq!(contract.base_uri, "https://gm.dev/{}");
+    assert_eq!(contract.balance_of(1, 2), 10);
+    assert_eq!(contract.balance_of(7, 2), 5);
+    assert_eq!(contract.total_supply(2), 15);
+    assert_eq!(contract.owned_tokens[&7], BTreeSet::from([2, 3]));
+    assert_eq!(contract.mint_policy(3), MintPolicy::Fixed);
+    assert!(contract.is_approved_for_all(1, 42));
+    assert_eq!(contract.get_token_metadata(3).unwrap().name, "sword");
+    assert_eq!(contract.get_token_metadata(3).unwrap().decimals, 0);
+    assert_eq!(contract.creator(3), None);
+}
+
+#[test]
+fn versioned_state_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig>::default();
+    contract.mint(1, 2, 3).unwrap(); // to, token, amount
+
+    let encoded = VersionedState::from(contract.clone()).encode();
+    assert_eq!(encoded[0], 2);
+    let state = VersionedState::<MockConfig>::decode(&mut &encoded[..]).unwrap();
+    assert_eq!(state.version(), STATE_VERSION);
+    assert_eq!(state.into_latest().balances, contract.balances);
+
+    let encoded = [&[1][..], &STATE_V1_FIXTURE[..]].concat();
+    let state = VersionedState::<MockConfig>::decode(&mut &encoded[..]).unwrap();
+    assert_eq!(state.version(), 1);
+    assert_eq!(state.into_latest().total_supply(2), 15);
+}
+
+#[test]
+fn indexer_works() {
+    let mut contract: Contract<MockConfig> = Contract::<MockConfig> {
+        mint_policies: BTreeMap::from([(2, MintPolicy::Unlimited)]),
+        metadata_registry: BTreeMap::from([
+            (1, TokenMetadata::default()),
+            (2, TokenMetadata::default()),
+        ]),
+        approvals: BTreeMap::from([(1, BTreeMap::from([(42, true), (43, false)]))]),
+        ..Default::default()
+    };
+    contract.mint(1, 1, 1).unwrap(); // to, token, amount
+    contract.mint(1, 2, 10).unwrap(); // to, token, amount
+    contract.mint(7, 2, 5).unwrap(); // to, token, amount
+
+    assert_eq!(
+        contract.all_balances(),
+        vec![(1, 1, 1), (2, 1, 10), (2, 7, 5)]
+    );
+    assert_eq!(contract.balances_of_owner(1), vec![(1, 1), (2, 10)]);
+    assert_eq!(contract.all_holders_of(2), vec![(1, 10), (7, 5)]);
+    assert_eq!(contract.all_approvals(1), vec![42]);
+    assert_eq!(contract.all_token_metadata(1, 10).len(), 1); // offset, limit
+    assert_eq!(
+        contract.stats(),
+        Stats {
+            tokens: 2,
+            holders: 2
+        }
+    );
+
+    contract.ctx.sender = 7;
+    contract.burn(7, 2, 5).unwrap(); // from, token, amount
+    assert_eq!(
+        contract.stats(),
+        Stats {
+            tokens: 2,
+            holders: 1
+        }
+    );
+
+    contract.ctx.program_id = 200;
+    contract.ctx.sender = 1;
+    contract.list(2, 4, 10).unwrap(); // token, amount, price
+    assert_eq!(
+        contract.stats(),
+        Stats {
+            tokens: 2,
+            holders: 1
+        }
+    );
+    assert_eq!(contract.all_holders_of(2), vec![(1, 6), (200, 4)]);
+}
@@ -11,13 +11,17 @@ impl<T: IConfig> IERC1155<T> for Contract<T> {
             .and_then(|kv| kv.get(&who))
             .unwrap_or(&T::Balance::zero())
     }
-    fn balance_of_batch(&self, who: Vec<T::AccountId>, token: Vec<T::TokenId>) -> Vec<T::Balance> {
-        self.check_balance_of_batch(who.clone(), token.clone());
-        token
+    fn balance_of_batch(
+        &self,
+        who: Vec<T::AccountId>,
+        token: Vec<T::TokenId>,
+    ) -> Result<Vec<T::Balance>, Error> {
+        self.check_balance_of_batch(who.clone(), token.clone())?;
+        Ok(token
             .iter()
             .zip(who)
             .map(|(token, account)| self.balance_of(account, *token))
-            .collect()
+            .collect())
     }
     fn safe_transfer_from(
         &mut self,
@@ -25,15 +29,10 @@ impl<T: IConfig> IERC1155<T> for Contract<T> {
         to: T::AccountId,
         token: T::TokenId,
         amount: T::Balance,
-    ) {
-        self.check_transfer_from(from, to, token, amount);
-        self.balances.entry(token).and_modify(|kv| {
-            kv.entry(from)
-                .and_modify(|v| *v = v.saturating_sub(&amount));
-            kv.entry(to)
-                .and_modify(|v| *v = v.saturating_add(&amount))
-                .or_insert(amount);
-        });
+    ) -> Result<(), Error> {
+        self.check_transfer_from(from, to, token, amount)?;
+        self.transfer_unchecked(from, to, token, amount);
+        Ok(())
     }
     fn safe_batch_transfer_from(
         &mut self,
@@ -41,30 +40,22 @@ impl<T: IConfig> IERC1155<T> for Contract<T> {
         to: T::AccountId,
         token: Vec<T::TokenId>,
         amount: Vec<T::Balance>,
-    ) {
-        self.check_batch_transfer_from(from, to, token.clone(), amount.clone());
-        token
-            .iter()
-            .enumerate()
-            .for_each(|(i, tk)| self.safe_transfer_from(from, to, *tk, amount[i]))
+    ) -> Result<(), Error> {
+        self.check_batch_transfer_from(from, to, token.clone(), amount.clone())?;
+        for (tk, am) in token.into_iter().zip(amount) {
+            self.transfer_unchecked(from, to, tk, am);
+        }
+        Ok(())
     }
     fn set_approval_for_all(
         &mut self,
         owner: T::AccountId,
         operator: T::AccountId,
         approved: bool,
-    ) {
-        self.check_set_approval_for_all(owner, operator, approved);
-        self.approvals
-            .entry(owner)
-            .and_modify(|kv| {
-                kv.entry(operator).and_modify(|v| *v = approved);
-            })
-            .or_insert({
-                let mut btm = BTreeMap::new();
-                btm.insert(operator, approved);
-                btm
-            });
+    ) -> Result<(), Error> {
+        self.check_set_approval_for_all(owner, operator, approved)?;
+        self.set_approval_for_all_unchecked(owner, operator, approved);
+        Ok(())
     }
     fn is_approved_for_all(&self, owner: T::AccountId, operator: T::AccountId) -> bool {
         *self
@@ -74,3 +65,51 @@ impl<T: IConfig> IERC1155<T> for Contract<T> {
             .unwrap_or(&false)
     }
 }
+
+impl<T: IConfig> Contract<T> {
+    /// records an approval that already passed `check_set_approval_for_all`
+    /// or `check_permit_for_all`
+    pub(crate) fn set_approval_for_all_unchecked(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+    ) {
+        self.approvals
+            .entry(owner)
+            .or_default()
+            .insert(operator, approved);
+    }
+    /// moves tokens that already passed `check_transfer_from`, or the
+    /// aggregated `check_batch_transfer_from`
+    pub(crate) fn transfer_unchecked(
+        &mut self,
+        from: T::AccountId,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) {
+        self.spend_allowance(from, self.sender(), token, amount);
+        self.move_balance(from, to, token, amount);
+    }
+    /// moves balance between accounts, also used to escrow listed units
+    pub(crate) fn move_balance(
+        &mut self,
+        from: T::AccountId,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) {
+        let kv = self.balances.entry(token).or_default();
+        let v = kv.entry(from).or_insert_with(T::Balance::zero);
+        *v = v
+            .checked_sub(&amount)
+            .expect("insufficient balance, see check_transfer_from");
+        let v = kv.entry(to).or_insert_with(T::Balance::zero);
+        *v = v
+            .checked_add(&amount)
+            .expect("balance overflow, see check_transfer_from");
+        self.update_owned_tokens(from, token);
+        self.update_owned_tokens(to, token);
+    }
+}
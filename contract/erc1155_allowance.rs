@@ -0,0 +1,66 @@
+//! contract implementation
+
+use crate::*;
+
+/// ERC1155Allowance interface
+impl<T: IConfig> IERC1155Allowance<T> for Contract<T> {
+    fn allowance(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        token: T::TokenId,
+    ) -> T::Balance {
+        *self
+            .allowances
+            .get(&owner)
+            .and_then(|x| x.get(&(operator, token)))
+            .unwrap_or(&T::Balance::zero())
+    }
+    fn approve(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        self.check_approve(owner, operator, token, amount)?;
+        let allowances = self.allowances.entry(owner).or_default();
+        if amount.is_zero() {
+            allowances.remove(&(operator, token));
+        } else {
+            allowances.insert((operator, token), amount);
+        }
+        if allowances.is_empty() {
+            self.allowances.remove(&owner);
+        }
+        Ok(())
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    /// consumes the allowance of an operator that is neither the owner nor
+    /// approved for all, called after the transfer or burn passed its checks
+    pub(crate) fn spend_allowance(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) {
+        if owner == operator || self.is_approved_for_all(owner, operator) {
+            return;
+        }
+        let Some(allowances) = self.allowances.get_mut(&owner) else {
+            return;
+        };
+        if let Some(v) = allowances.get_mut(&(operator, token)) {
+            *v = v.saturating_sub(&amount);
+            if v.is_zero() {
+                allowances.remove(&(operator, token));
+            }
+        }
+        if allowances.is_empty() {
+            self.allowances.remove(&owner);
+        }
+    }
+}
@@ -2,6 +2,18 @@
 
 use crate::*;
 
+/// limits on token metadata fields, in bytes or entries
+const MAX_NAME_LEN: usize = 128;
+const MAX_DESCRIPTION_LEN: usize = 2048;
+const MAX_URI_LEN: usize = 512;
+const MAX_ATTRIBUTES: usize = 64;
+const MAX_ATTRIBUTE_LEN: usize = 128;
+/// 10^38 is the largest power of ten within u128
+const MAX_DECIMALS: u8 = 38;
+
+/// every event costs a message per subscriber
+const MAX_SUBSCRIBERS: usize = 16;
+
 /// ERC1155Check interface
 impl<T: IConfig> IERC1155Check<T> for Contract<T> {
     fn check_transfer_from(
@@ -10,22 +22,26 @@ impl<T: IConfig> IERC1155Check<T> for Contract<T> {
         to: T::AccountId,
         token: T::TokenId,
         amount: T::Balance,
-    ) {
+    ) -> Result<(), Error> {
+        self.check_not_paused(token)?;
         if amount.is_zero() {
-            panic!("check failed: cannot transfer 0 amount")
+            return Err(Error::ZeroAmount);
         }
+        self.check_not_escrow(from)?;
         if from != self.sender() && !self.is_approved_for_all(from, self.sender()) {
-            panic!("check failed: needs approval")
-        }
-        if self.balance_of(from, token) < amount {
-            panic!("check failed: insufficient balance")
+            self.check_allowance(from, token, amount)?
         }
+        self.check_unlocked_balance(from, token, amount)?;
         if from == to {
-            panic!("check failed: self transfer not permitted")
+            return Err(Error::SelfTransfer);
         }
         if to == T::AccountId::default() {
-            panic!("check failed: transfer to black hole not permitted")
+            return Err(Error::ZeroAddress);
         }
+        if self.balance_of(to, token).checked_add(&amount).is_none() {
+            return Err(Error::BalanceOverflow);
+        }
+        Ok(())
     }
     fn check_batch_transfer_from(
         &self,
@@ -33,79 +49,625 @@ impl<T: IConfig> IERC1155Check<T> for Contract<T> {
         to: T::AccountId,
         token: Vec<T::TokenId>,
         amount: Vec<T::Balance>,
-    ) {
-        if token.len() != amount.len() {
-            panic!("check failed: token and amount length mismatch")
-        }
-        for (tk, am) in token.iter().zip(amount) {
-            self.check_transfer_from(from, to, *tk, am)
+    ) -> Result<(), Error> {
+        for (tk, am) in Self::aggregate_batch(token, amount)? {
+            self.check_transfer_from(from, to, tk, am)?
         }
+        Ok(())
     }
-    fn check_mint(&self, to: T::AccountId, token: T::TokenId, amount: T::Balance) {
-        if to == T::AccountId::default() {
-            panic!("check failed: cannot mint to black hole address")
-        }
-        if amount.is_zero() {
-            panic!("check failed: cannot mint 0 amount")
-        }
-        if self.balances.contains_key(&token) {
-            panic!("check failed: cannot mint twice")
+    fn check_mint(
+        &self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        self.check_not_paused(token)?;
+        if !self.has_role(self.sender(), Role::Minter) {
+            return Err(Error::MissingRole(Role::Minter));
         }
+        self.check_mint_policy(to, token, amount)
     }
-    fn check_mint_batch(&self, to: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>) {
-        if token.len() != amount.len() {
-            panic!("check failed: token and amount length mismatch")
-        }
-        for (tk, am) in token.iter().zip(amount) {
-            self.check_mint(to, *tk, am)
+    fn check_mint_batch(
+        &self,
+        to: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error> {
+        for (tk, am) in Self::aggregate_batch(token, amount)? {
+            self.check_mint(to, tk, am)?
         }
+        Ok(())
     }
-    fn check_balance_of_batch(&self, who: Vec<T::AccountId>, token: Vec<T::TokenId>) {
+    fn check_balance_of_batch(
+        &self,
+        who: Vec<T::AccountId>,
+        token: Vec<T::TokenId>,
+    ) -> Result<(), Error> {
         if who.len() != token.len() {
-            panic!("check failed: token and account length mismatch")
+            return Err(Error::LengthMismatch);
         }
+        Ok(())
     }
     fn check_set_approval_for_all(
         &self,
         owner: T::AccountId,
         _operator: T::AccountId,
         _approved: bool,
-    ) {
+    ) -> Result<(), Error> {
+        if owner != self.sender() {
+            return Err(Error::NotAccountOwner);
+        }
+        Ok(())
+    }
+    fn check_approve(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        _token: T::TokenId,
+        _amount: T::Balance,
+    ) -> Result<(), Error> {
         if owner != self.sender() {
-            panic!("check failed: sender is not account owner")
+            return Err(Error::NotAccountOwner);
+        }
+        if operator == T::AccountId::default() {
+            return Err(Error::ZeroAddress);
+        }
+        Ok(())
+    }
+    fn check_permit_for_all(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+        deadline: u64,
+        nonce: u64,
+        signature: Signature,
+    ) -> Result<(), Error> {
+        if operator == T::AccountId::default() {
+            return Err(Error::ZeroAddress);
+        }
+        let message = self.permit_message(owner, operator, approved, deadline, nonce);
+        self.check_signed(owner, nonce, deadline, &message, signature)
+    }
+    fn check_execute_signed(
+        &self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+        signature: Signature,
+    ) -> Result<(), Error> {
+        let message = self.signed_message(signer, nonce, deadline, payload);
+        self.check_signed(signer, nonce, deadline, &message, signature)
+    }
+    fn check_set_price(&self, _token: T::TokenId, price: Option<T::Balance>) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        if price.is_some_and(|price| price.is_zero()) {
+            return Err(Error::ZeroAmount);
+        }
+        Ok(())
+    }
+    fn check_buy(&self, token: T::TokenId, amount: T::Balance) -> Result<(), Error> {
+        self.check_not_paused(token)?;
+        let price = self.price(token).ok_or(Error::NotForSale)?;
+        self.check_mint_policy(self.sender(), token, amount)?;
+        match price.checked_mul(&amount) {
+            Some(cost) if cost <= self.ctx.value() => {
+                self.proceeds()
+                    .checked_add(&cost)
+                    .ok_or(Error::BalanceOverflow)?;
+                Ok(())
+            }
+            _ => Err(Error::InsufficientValue),
+        }
+    }
+    fn check_withdraw(&self) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        if self.proceeds().is_zero() {
+            return Err(Error::ZeroAmount);
         }
+        Ok(())
     }
-    fn check_burn(&self, from: T::AccountId, token: T::TokenId, amount: T::Balance) {
+    fn check_list(
+        &self,
+        token: T::TokenId,
+        amount: T::Balance,
+        price: T::Balance,
+    ) -> Result<(), Error> {
+        self.check_not_paused(token)?;
+        if amount.is_zero() || price.is_zero() {
+            return Err(Error::ZeroAmount);
+        }
+        self.check_unlocked_balance(self.sender(), token, amount)?;
+        Ok(())
+    }
+    fn check_cancel(&self, listing_id: u64) -> Result<(), Error> {
+        let listing = self.listing(listing_id).ok_or(Error::NoSuchListing)?;
+        self.check_not_paused(listing.token)?;
+        if listing.seller != self.sender() {
+            return Err(Error::NotAccountOwner);
+        }
+        Ok(())
+    }
+    fn check_buy_listing(&self, listing_id: u64, amount: T::Balance) -> Result<(), Error> {
+        let listing = self.listing(listing_id).ok_or(Error::NoSuchListing)?;
+        self.check_not_paused(listing.token)?;
         if amount.is_zero() {
-            panic!("check failed: cannot burn 0 amount")
+            return Err(Error::ZeroAmount);
         }
-        if from != self.sender() && !self.is_approved_for_all(from, self.sender()) {
-            panic!("check failed: needs approval")
+        if listing.amount < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        if listing.seller == self.sender() {
+            return Err(Error::SelfTransfer);
+        }
+        match listing.price.checked_mul(&amount) {
+            Some(cost) if cost <= self.ctx.value() => Ok(()),
+            _ => Err(Error::InsufficientValue),
+        }
+    }
+    fn check_mint_vested(
+        &self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+        _start: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<(), Error> {
+        self.check_mint(to, token, amount)?;
+        if cliff > duration {
+            return Err(Error::InvalidSchedule);
+        }
+        Ok(())
+    }
+    fn check_release(&self, token: T::TokenId) -> Result<(), Error> {
+        if self.releasable(self.sender(), token).is_zero() {
+            return Err(Error::ZeroAmount);
+        }
+        Ok(())
+    }
+    fn check_import_snapshot(&self, entries: &[SnapshotEntryOf<T>]) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        if !self.is_paused() {
+            return Err(Error::NotPaused);
         }
-        if self.balance_of(from, token) < amount {
-            panic!("check failed: insufficient balance")
+        let mut supply = BTreeMap::new();
+        let mut holders = BTreeSet::new();
+        let mut listing_ids = BTreeSet::new();
+        for entry in entries {
+            let (token, amount) = match entry {
+                SnapshotEntry::Balance {
+                    token,
+                    owner,
+                    amount,
+                } => {
+                    if *owner == T::AccountId::default() {
+                        return Err(Error::ZeroAddress);
+                    }
+                    if amount.is_zero() {
+                        return Err(Error::ZeroAmount);
+                    }
+                    self.check_not_escrow(*owner)?;
+                    // balances are imported once, never merged
+                    if !self.balance_of(*owner, *token).is_zero()
+                        || !holders.insert((*token, *owner))
+                    {
+                        return Err(Error::AlreadyMinted);
+                    }
+                    (token, amount)
+                }
+                SnapshotEntry::Listing {
+                    listing_id,
+                    listing,
+                } => {
+                    if listing.seller == T::AccountId::default() {
+                        return Err(Error::ZeroAddress);
+                    }
+                    if listing.amount.is_zero() || listing.price.is_zero() {
+                        return Err(Error::ZeroAmount);
+                    }
+                    // listings are imported once, never merged
+                    if self.listings.contains_key(listing_id) || !listing_ids.insert(*listing_id) {
+                        return Err(Error::AlreadyMinted);
+                    }
+                    (&listing.token, &listing.amount)
+                }
+                SnapshotEntry::VestingSchedule { owner, schedule } => {
+                    if *owner == T::AccountId::default() {
+                        return Err(Error::ZeroAddress);
+                    }
+                    if schedule.amount.is_zero() {
+                        return Err(Error::ZeroAmount);
+                    }
+                    if schedule.cliff > schedule.duration || schedule.released > schedule.amount {
+                        return Err(Error::InvalidSchedule);
+                    }
+                    continue;
+                }
+                SnapshotEntry::Metadata { metadata, .. } => {
+                    Self::check_metadata(metadata)?;
+                    continue;
+                }
+                SnapshotEntry::Role { account, .. } => {
+                    if *account == T::AccountId::default() {
+                        return Err(Error::ZeroAddress);
+                    }
+                    continue;
+                }
+                SnapshotEntry::DefaultRoyalty { royalty }
+                | SnapshotEntry::TokenRoyalty { royalty, .. } => {
+                    Self::check_royalty(Some(*royalty))?;
+                    continue;
+                }
+                SnapshotEntry::Price { price, .. } => {
+                    if price.is_zero() {
+                        return Err(Error::ZeroAmount);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            let total = supply
+                .entry(*token)
+                .or_insert_with(|| self.total_supply(*token));
+            *total = total.checked_add(amount).ok_or(Error::BalanceOverflow)?;
         }
+        Ok(())
+    }
+    fn check_set_default_royalty(
+        &self,
+        royalty: Option<Royalty<T::AccountId>>,
+    ) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        Self::check_royalty(royalty)
+    }
+    fn check_set_token_royalty(
+        &self,
+        token: T::TokenId,
+        royalty: Option<Royalty<T::AccountId>>,
+    ) -> Result<(), Error> {
+        if !self.has_role(self.sender(), Role::MetadataAdmin) {
+            return Err(Error::MissingRole(Role::MetadataAdmin));
+        }
+        if !self.balances.contains_key(&token) {
+            return Err(Error::NoSuchToken);
+        }
+        Self::check_royalty(royalty)
+    }
+    fn check_burn(
+        &self,
+        from: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        self.check_not_paused(token)?;
+        if amount.is_zero() {
+            return Err(Error::ZeroAmount);
+        }
+        self.check_not_escrow(from)?;
+        // roles don't bypass approvals, no one can burn tokens of an account
+        // without its consent
+        if from != self.sender() && !self.is_approved_for_all(from, self.sender()) {
+            self.check_allowance(from, token, amount)?
+        }
+        self.check_unlocked_balance(from, token, amount)?;
+        Ok(())
     }
     fn check_burn_batch(
         &self,
         from: T::AccountId,
         token: Vec<T::TokenId>,
         amount: Vec<T::Balance>,
-    ) {
-        if token.len() != amount.len() {
-            panic!("check failed: token and amount length mismatch")
+    ) -> Result<(), Error> {
+        for (tk, am) in Self::aggregate_batch(token, amount)? {
+            self.check_burn(from, tk, am)?
+        }
+        Ok(())
+    }
+    // allow creator of token or metadata admin to update metadata
+    fn check_update_token_metadata(
+        &self,
+        token: T::TokenId,
+        metadata: Option<TokenMetadata>,
+    ) -> Result<(), Error> {
+        self.check_metadata_editable(token)?;
+        metadata.as_ref().map_or(Ok(()), Self::check_metadata)
+    }
+    // allow creator of token or metadata admin to update token uri
+    fn check_set_token_uri(&self, token: T::TokenId, _uri: Option<T::Text>) -> Result<(), Error> {
+        self.check_metadata_editable(token)
+    }
+    // allow creator of token or metadata admin to freeze metadata, once
+    fn check_freeze_metadata(&self, token: T::TokenId) -> Result<(), Error> {
+        self.check_metadata_editable(token)
+    }
+    fn check_grant_role(&self, account: T::AccountId, _role: Role) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        if account == T::AccountId::default() {
+            return Err(Error::ZeroAddress);
+        }
+        Ok(())
+    }
+    fn check_revoke_role(&self, _account: T::AccountId, _role: Role) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        Ok(())
+    }
+    fn check_renounce_role(&self, account: T::AccountId, _role: Role) -> Result<(), Error> {
+        if account != self.sender() {
+            return Err(Error::NotAccountOwner);
+        }
+        Ok(())
+    }
+    fn check_transfer_ownership(&self, new_owner: T::AccountId) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        if new_owner == T::AccountId::default() {
+            return Err(Error::ZeroAddress);
+        }
+        Ok(())
+    }
+    fn check_create_token(
+        &self,
+        token: T::TokenId,
+        policy: MintPolicy<T::Balance>,
+    ) -> Result<(), Error> {
+        if !self.has_role(self.sender(), Role::Minter) {
+            return Err(Error::MissingRole(Role::Minter));
+        }
+        if self.mint_policies.contains_key(&token) || self.balances.contains_key(&token) {
+            return Err(Error::TokenAlreadyExists);
+        }
+        if policy
+            == (MintPolicy::Capped {
+                max_supply: T::Balance::zero(),
+            })
+        {
+            return Err(Error::ZeroAmount);
+        }
+        Ok(())
+    }
+    fn check_register_receiver(&self, who: T::AccountId) -> Result<(), Error> {
+        if who != self.sender() {
+            return Err(Error::NotAccountOwner);
+        }
+        Ok(())
+    }
+    fn check_unregister_receiver(&self, who: T::AccountId) -> Result<(), Error> {
+        if who != self.sender() {
+            return Err(Error::NotAccountOwner);
+        }
+        Ok(())
+    }
+    fn check_pause(&self) -> Result<(), Error> {
+        if !self.has_role(self.sender(), Role::Pauser) {
+            return Err(Error::MissingRole(Role::Pauser));
+        }
+        if self.is_paused() {
+            return Err(Error::Paused);
+        }
+        Ok(())
+    }
+    fn check_unpause(&self) -> Result<(), Error> {
+        if !self.has_role(self.sender(), Role::Pauser) {
+            return Err(Error::MissingRole(Role::Pauser));
+        }
+        if !self.is_paused() {
+            return Err(Error::NotPaused);
+        }
+        Ok(())
+    }
+    fn check_pause_token(&self, token: T::TokenId) -> Result<(), Error> {
+        if !self.has_role(self.sender(), Role::Pauser) {
+            return Err(Error::MissingRole(Role::Pauser));
+        }
+        if self.is_token_paused(token) {
+            return Err(Error::TokenPaused);
+        }
+        Ok(())
+    }
+    fn check_unpause_token(&self, token: T::TokenId) -> Result<(), Error> {
+        if !self.has_role(self.sender(), Role::Pauser) {
+            return Err(Error::MissingRole(Role::Pauser));
+        }
+        if !self.is_token_paused(token) {
+            return Err(Error::NotPaused);
+        }
+        Ok(())
+    }
+    // the owner manages the subscribers, so that no one else can add to the
+    // cost of every action
+    fn check_subscribe(&self, who: T::AccountId) -> Result<(), Error> {
+        if self.sender() != self.owner {
+            return Err(Error::NotContractOwner);
+        }
+        if !self.is_subscriber(who) && self.subscribers.len() >= MAX_SUBSCRIBERS {
+            return Err(Error::TooManySubscribers);
+        }
+        Ok(())
+    }
+    fn check_unsubscribe(&self, who: T::AccountId) -> Result<(), Error> {
+        if who != self.sender() && self.sender() != self.owner {
+            return Err(Error::NotAccountOwner);
+        }
+        Ok(())
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    /// checks that the sender may move `amount` of `from`'s token via allowance
+    fn check_allowance(
+        &self,
+        from: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        let allowance = self.allowance(from, self.sender(), token);
+        if allowance.is_zero() {
+            return Err(Error::NeedsApproval);
+        }
+        if allowance < amount {
+            return Err(Error::InsufficientAllowance);
+        }
+        Ok(())
+    }
+    /// checks that `from` isn't the contract itself, whose balance backs the
+    /// open listings
+    fn check_not_escrow(&self, from: T::AccountId) -> Result<(), Error> {
+        if from == self.ctx.program_id() {
+            return Err(Error::EscrowBalance);
+        }
+        Ok(())
+    }
+    /// checks that `amount` of the token can be minted to `to` under its
+    /// mint policy, regardless of who mints
+    fn check_mint_policy(
+        &self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        if to == T::AccountId::default() {
+            return Err(Error::ZeroAddress);
+        }
+        if amount.is_zero() {
+            return Err(Error::ZeroAmount);
+        }
+        // balances are bounded by the total supply
+        let total_supply = self
+            .total_supply(token)
+            .checked_add(&amount)
+            .ok_or(Error::BalanceOverflow)?;
+        match self.mint_policy(token) {
+            MintPolicy::Fixed => {
+                if self.balances.contains_key(&token) {
+                    return Err(Error::AlreadyMinted);
+                }
+            }
+            MintPolicy::Capped { max_supply } => {
+                if total_supply > max_supply {
+                    return Err(Error::MaxSupplyExceeded);
+                }
+            }
+            MintPolicy::Unlimited => {}
+        }
+        Ok(())
+    }
+    /// checks the deadline, nonce and signature shared by permits and
+    /// meta-transactions
+    fn check_signed(
+        &self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        message: &[u8],
+        signature: Signature,
+    ) -> Result<(), Error> {
+        if self.ctx.block_timestamp() > deadline {
+            return Err(Error::PermitExpired);
+        }
+        if nonce != self.nonce(signer) {
+            return Err(Error::InvalidNonce);
         }
-        for (tk, am) in token.iter().zip(amount) {
-            self.check_burn(from, *tk, am)
+        if !self.ctx.verify(signer, message, signature) {
+            return Err(Error::InvalidSignature);
         }
+        Ok(())
     }
-    // allow owner of token to update metadata
-    fn check_update_token_metadata(&self, token: T::TokenId, _metadata: Option<TokenMetadata>) {
+    /// checks that `owner` holds `amount` of the token outside of vesting
+    /// schedules
+    fn check_unlocked_balance(
+        &self,
+        owner: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        let balance = self.balance_of(owner, token);
+        if balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+        if balance.saturating_sub(&self.locked_balance_of(owner, token)) < amount {
+            return Err(Error::BalanceLocked);
+        }
+        Ok(())
+    }
+    /// checks that the sender may edit the metadata and uri of the token
+    fn check_metadata_editable(&self, token: T::TokenId) -> Result<(), Error> {
+        self.check_not_paused(token)?;
         if !self.balances.contains_key(&token) {
-            panic!("check failed: no such token")
+            return Err(Error::NoSuchToken);
+        }
+        if self.is_metadata_frozen(token) {
+            return Err(Error::MetadataFrozen);
+        }
+        if self.creator(token) != Some(self.sender())
+            && !self.has_role(self.sender(), Role::MetadataAdmin)
+        {
+            return Err(Error::NotTokenCreator);
+        }
+        Ok(())
+    }
+    /// checks the metadata fields against their limits
+    fn check_metadata(metadata: &TokenMetadata) -> Result<(), Error> {
+        let uris = [
+            &metadata.image_uri,
+            &metadata.json_uri,
+            &metadata.animation_url,
+            &metadata.external_url,
+        ];
+        if metadata.name.len() > MAX_NAME_LEN
+            || metadata.description.len() > MAX_DESCRIPTION_LEN
+            || uris.iter().any(|uri| uri.len() > MAX_URI_LEN)
+            || metadata.attributes.len() > MAX_ATTRIBUTES
+            || metadata.attributes.iter().any(|(trait_type, value)| {
+                trait_type.len() > MAX_ATTRIBUTE_LEN || value.len() > MAX_ATTRIBUTE_LEN
+            })
+            || metadata.decimals > MAX_DECIMALS
+        {
+            return Err(Error::InvalidMetadata);
+        }
+        Ok(())
+    }
+    /// checks that neither the contract nor the token is paused
+    fn check_not_paused(&self, token: T::TokenId) -> Result<(), Error> {
+        if self.is_paused() {
+            return Err(Error::Paused);
+        }
+        if self.is_token_paused(token) {
+            return Err(Error::TokenPaused);
+        }
+        Ok(())
+    }
+    /// sums the amounts of a batch per token id so that a batch is validated
+    /// against the state it would produce, rejecting zero amounts
+    fn aggregate_batch(
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<BTreeMap<T::TokenId, T::Balance>, Error> {
+        if token.len() != amount.len() {
+            return Err(Error::LengthMismatch);
         }
-        if self.balance_of(self.sender(), token).is_zero() {
-            panic!("check failed: not token owner")
+        let mut totals: BTreeMap<T::TokenId, T::Balance> = BTreeMap::new();
+        for (tk, am) in token.into_iter().zip(amount) {
+            if am.is_zero() {
+                return Err(Error::ZeroAmount);
+            }
+            let total = totals.entry(tk).or_insert_with(T::Balance::zero);
+            *total = total.checked_add(&am).ok_or(Error::BalanceOverflow)?;
         }
+        Ok(totals)
     }
 }
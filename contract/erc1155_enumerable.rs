@@ -0,0 +1,62 @@
+//! contract implementation
+
+use crate::*;
+
+/// ERC1155Enumerable interface
+impl<T: IConfig> IERC1155Enumerable<T> for Contract<T> {
+    fn tokens(&self, offset: u32, limit: u32) -> Vec<T::TokenId> {
+        self.total_supply
+            .iter()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(k, _)| *k)
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+    fn holders_of(
+        &self,
+        token: T::TokenId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(T::AccountId, T::Balance)> {
+        self.balances
+            .get(&token)
+            .into_iter()
+            .flatten()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(k, v)| (*k, *v))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+    fn tokens_of(
+        &self,
+        owner: T::AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(T::TokenId, T::Balance)> {
+        self.owned_tokens
+            .get(&owner)
+            .into_iter()
+            .flatten()
+            .map(|token| (*token, self.balance_of(owner, *token)))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    /// keeps the account to token ids index in sync with the balance of
+    /// `who`, called after every balance change
+    pub(crate) fn update_owned_tokens(&mut self, who: T::AccountId, token: T::TokenId) {
+        if !self.balance_of(who, token).is_zero() {
+            self.owned_tokens.entry(who).or_default().insert(token);
+        } else if let Some(tokens) = self.owned_tokens.get_mut(&who) {
+            tokens.remove(&token);
+            if tokens.is_empty() {
+                self.owned_tokens.remove(&who);
+            }
+        }
+    }
+}
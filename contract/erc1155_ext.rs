@@ -10,40 +10,101 @@ impl<T: IConfig> IERC1155Ext<T> for Contract<T> {
     fn symbol(&self) -> T::Text {
         self.symbol.clone()
     }
-    fn burn(&mut self, from: T::AccountId, token: T::TokenId, amount: T::Balance) {
-        self.check_burn(from, token, amount);
+    fn burn(
+        &mut self,
+        from: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        self.check_burn(from, token, amount)?;
+        self.burn_unchecked(from, token, amount);
+        Ok(())
+    }
+    fn burn_batch(
+        &mut self,
+        from: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error> {
+        self.check_burn_batch(from, token.clone(), amount.clone())?;
+        for (tk, am) in token.into_iter().zip(amount) {
+            self.burn_unchecked(from, tk, am);
+        }
+        Ok(())
+    }
+    fn mint(
+        &mut self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error> {
+        self.check_mint(to, token, amount)?;
+        self.record_creator(token, self.sender());
+        self.mint_unchecked(to, token, amount);
+        Ok(())
+    }
+    fn mint_batch(
+        &mut self,
+        to: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error> {
+        self.check_mint_batch(to, token.clone(), amount.clone())?;
+        for (tk, am) in token.into_iter().zip(amount) {
+            self.record_creator(tk, self.sender());
+            self.mint_unchecked(to, tk, am);
+        }
+        Ok(())
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    /// burns tokens that already passed `check_burn`, or the aggregated
+    /// `check_burn_batch`
+    pub(crate) fn burn_unchecked(
+        &mut self,
+        from: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) {
+        self.spend_allowance(from, self.sender(), token, amount);
         self.balances.entry(token).and_modify(|kv| {
             kv.entry(from)
                 .and_modify(|v| *v = v.saturating_sub(&amount));
         });
+        self.total_supply
+            .entry(token)
+            .and_modify(|v| *v = v.saturating_sub(&amount));
+        self.update_owned_tokens(from, token);
     }
-    fn burn_batch(&mut self, from: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>) {
-        self.check_burn_batch(from, token.clone(), amount.clone());
-        token
-            .iter()
-            .enumerate()
-            .for_each(|(i, tk)| self.burn(from, *tk, amount[i]))
-    }
-    fn mint(&mut self, to: T::AccountId, token: T::TokenId, amount: T::Balance) {
-        self.check_mint(to, token, amount);
-        self.balances
+    /// mints tokens that already passed `check_mint`, or the aggregated
+    /// `check_mint_batch`
+    pub(crate) fn mint_unchecked(
+        &mut self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) {
+        let balance = self
+            .balances
             .entry(token)
-            .and_modify(|kv| {
-                kv.entry(to)
-                    .and_modify(|v| *v = v.saturating_add(&amount))
-                    .or_insert(amount);
-            })
-            .or_insert({
-                let mut btm = BTreeMap::new();
-                btm.insert(to, amount);
-                btm
-            });
-    }
-    fn mint_batch(&mut self, to: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>) {
-        self.check_mint_batch(to, token.clone(), amount.clone());
-        token
-            .iter()
-            .enumerate()
-            .for_each(|(i, tk)| self.mint(to, *tk, amount[i]))
+            .or_default()
+            .entry(to)
+            .or_insert_with(T::Balance::zero);
+        *balance = balance
+            .checked_add(&amount)
+            .expect("balance overflow, see check_mint_policy");
+        let total_supply = self
+            .total_supply
+            .entry(token)
+            .or_insert_with(T::Balance::zero);
+        *total_supply = total_supply
+            .checked_add(&amount)
+            .expect("total supply overflow, see check_mint_policy");
+        self.update_owned_tokens(to, token);
+    }
+    /// records the creator of a token on its first mint
+    pub(crate) fn record_creator(&mut self, token: T::TokenId, creator: T::AccountId) {
+        self.creators.entry(token).or_insert(creator);
     }
 }
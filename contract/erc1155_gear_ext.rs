@@ -4,16 +4,210 @@ use crate::*;
 use config::GearConfig;
 use io::*;
 
+impl Contract<GearConfig> {
+    /// replies the event to the caller and sends a copy to every subscriber
+    /// and involved party other than the caller, this program, the zero
+    /// address and registered receivers, which are notified through the
+    /// receiver hook
+    fn emit_event(&self, event: Event, parties: &[ActorId]) {
+        self.emit_event_with_value(event, parties, 0, None);
+    }
+    /// `emit_event` with `value` attached to the reply and `payment` to the
+    /// copy sent to its recipient, who receives it even if not involved
+    fn emit_event_with_value(
+        &self,
+        event: Event,
+        parties: &[ActorId],
+        value: u128,
+        payment: Option<(ActorId, u128)>,
+    ) {
+        if let Some((payee, amount)) = payment {
+            gstd::msg::send(payee, event.clone(), amount).expect("Failed to send payment");
+        }
+        let recipients: BTreeSet<ActorId> = parties
+            .iter()
+            .filter(|x| !self.is_receiver(**x))
+            .chain(self.subscribers.iter())
+            .filter(|x| {
+                ![
+                    gstd::msg::source(),
+                    gstd::exec::program_id(),
+                    ActorId::zero(),
+                ]
+                .contains(*x)
+                    && !payment.is_some_and(|(payee, _)| payee == **x)
+            })
+            .copied()
+            .collect();
+        for recipient in recipients {
+            gstd::msg::send(recipient, event.clone(), 0).expect("Failed to send event");
+        }
+        gstd::msg::reply(event, value).expect("Failed to reply event");
+    }
+}
+
 /// ERC1155GearExt interface
 impl IERC1155GearExt for Contract<GearConfig> {
     fn emit_update_token_metadata_event(&self, token: u128, metadata: Option<TokenMetadata>) {
-        gstd::msg::reply(Event::UpdateTokenMetadata { token, metadata }, 0)
-            .expect("Failed to reply Event::UpdateTokenMetadata");
+        self.emit_event(Event::UpdateTokenMetadata { token, metadata }, &[]);
+    }
+    fn emit_role_granted_event(&self, account: ActorId, role: Role, sender: ActorId) {
+        self.emit_event(
+            Event::RoleGranted {
+                account,
+                role,
+                sender,
+            },
+            &[account],
+        );
+    }
+    fn emit_role_revoked_event(&self, account: ActorId, role: Role, sender: ActorId) {
+        self.emit_event(
+            Event::RoleRevoked {
+                account,
+                role,
+                sender,
+            },
+            &[account],
+        );
+    }
+    fn emit_ownership_transferred_event(&self, previous_owner: ActorId, new_owner: ActorId) {
+        self.emit_event(
+            Event::OwnershipTransferred {
+                previous_owner,
+                new_owner,
+            },
+            &[previous_owner, new_owner],
+        );
+    }
+    fn emit_token_created_event(&self, token: u128, policy: MintPolicy) {
+        self.emit_event(Event::TokenCreated { token, policy }, &[]);
+    }
+    fn emit_receiver_registered_event(&self, receiver: ActorId) {
+        self.emit_event(Event::ReceiverRegistered { receiver }, &[]);
+    }
+    fn emit_receiver_unregistered_event(&self, receiver: ActorId) {
+        self.emit_event(Event::ReceiverUnregistered { receiver }, &[]);
+    }
+    fn emit_paused_event(&self, account: ActorId) {
+        self.emit_event(Event::Paused { account }, &[]);
+    }
+    fn emit_unpaused_event(&self, account: ActorId) {
+        self.emit_event(Event::Unpaused { account }, &[]);
+    }
+    fn emit_token_paused_event(&self, token: u128, account: ActorId) {
+        self.emit_event(Event::TokenPaused { token, account }, &[]);
+    }
+    fn emit_token_unpaused_event(&self, token: u128, account: ActorId) {
+        self.emit_event(Event::TokenUnpaused { token, account }, &[]);
+    }
+    fn emit_price_set_event(&self, token: u128, price: Option<u128>) {
+        self.emit_event(Event::PriceSet { token, price }, &[]);
+    }
+    fn emit_purchased_event(
+        &self,
+        buyer: ActorId,
+        token: u128,
+        amount: u128,
+        cost: u128,
+        refund: u128,
+    ) {
+        self.emit_event_with_value(
+            Event::Purchased {
+                buyer,
+                token,
+                amount,
+                cost,
+            },
+            &[],
+            refund,
+            None,
+        );
+    }
+    fn emit_withdrawn_event(&self, to: ActorId, amount: u128) {
+        self.emit_event_with_value(Event::Withdrawn { to, amount }, &[], amount, None);
+    }
+    fn emit_released_event(&self, owner: ActorId, token: u128, amount: u128) {
+        self.emit_event(
+            Event::Released {
+                owner,
+                token,
+                amount,
+            },
+            &[],
+        );
+    }
+    fn emit_snapshot_imported_event(&self, entries: u32, checksum: [u8; 32]) {
+        self.emit_event(Event::SnapshotImported { entries, checksum }, &[]);
+    }
+    fn emit_listed_event(
+        &self,
+        listing_id: u64,
+        seller: ActorId,
+        token: u128,
+        amount: u128,
+        price: u128,
+    ) {
+        self.emit_event(
+            Event::Listed {
+                listing_id,
+                seller,
+                token,
+                amount,
+                price,
+            },
+            &[],
+        );
+    }
+    fn emit_sold_event(
+        &self,
+        listing_id: u64,
+        buyer: ActorId,
+        seller: ActorId,
+        amount: u128,
+        cost: u128,
+        refund: u128,
+    ) {
+        self.emit_event_with_value(
+            Event::Sold {
+                listing_id,
+                buyer,
+                seller,
+                amount,
+                cost,
+            },
+            &[],
+            refund,
+            Some((seller, cost)),
+        );
+    }
+    fn emit_cancelled_event(&self, listing_id: u64) {
+        self.emit_event(Event::Cancelled { listing_id }, &[]);
+    }
+    fn emit_default_royalty_set_event(&self, royalty: Option<Royalty>) {
+        self.emit_event(Event::DefaultRoyaltySet { royalty }, &[]);
+    }
+    fn emit_token_royalty_set_event(&self, token: u128, royalty: Option<Royalty>) {
+        self.emit_event(Event::TokenRoyaltySet { token, royalty }, &[]);
+    }
+    fn emit_subscribed_event(&self, subscriber: ActorId) {
+        self.emit_event(Event::Subscribed { subscriber }, &[]);
+    }
+    fn emit_unsubscribed_event(&self, subscriber: ActorId) {
+        self.emit_event(Event::Unsubscribed { subscriber }, &[]);
+    }
+    fn emit_error_event(&self, error: Error) {
+        // refunds the value attached to the failed message
+        gstd::msg::reply(Event::Error(error), gstd::msg::value())
+            .expect("Failed to reply Event::Error");
     }
     fn emit_whoami_event(&self) {
         gstd::msg::reply(
             Event::Whoami {
                 sender: self.sender(),
+                origin: self.ctx.origin(),
+                block_height: self.ctx.block_height(),
+                block_timestamp: self.ctx.block_timestamp(),
             },
             0,
         )
@@ -27,7 +221,7 @@ impl IERC1155GearExt for Contract<GearConfig> {
         token: u128,
         amount: u128,
     ) {
-        gstd::msg::reply(
+        self.emit_event(
             Event::TransferSingle {
                 operator,
                 from,
@@ -35,9 +229,8 @@ impl IERC1155GearExt for Contract<GearConfig> {
                 token,
                 amount,
             },
-            0,
-        )
-        .expect("Failed to reply Event::TransferSingle");
+            &[operator, from, to],
+        );
     }
     fn emit_transfer_batch_event(
         &self,
@@ -47,7 +240,7 @@ impl IERC1155GearExt for Contract<GearConfig> {
         token: Vec<u128>,
         amount: Vec<u128>,
     ) {
-        gstd::msg::reply(
+        self.emit_event(
             Event::TransferBatch {
                 operator,
                 from,
@@ -55,22 +248,34 @@ impl IERC1155GearExt for Contract<GearConfig> {
                 token,
                 amount,
             },
-            0,
-        )
-        .expect("Failed to reply Event::TransferBatch");
+            &[operator, from, to],
+        );
     }
     fn emit_approval_for_all_event(&self, owner: ActorId, operator: ActorId, approved: bool) {
-        gstd::msg::reply(
+        self.emit_event(
             Event::ApprovedForAll {
                 owner,
                 operator,
                 approved,
             },
-            0,
-        )
-        .expect("Failed to reply Event::ApprovedForAll");
+            &[owner, operator],
+        );
+    }
+    fn emit_approval_event(&self, owner: ActorId, operator: ActorId, token: u128, amount: u128) {
+        self.emit_event(
+            Event::Approval {
+                owner,
+                operator,
+                token,
+                amount,
+            },
+            &[owner, operator],
+        );
     }
     fn emit_uri_event(&self, value: String, token: u128) {
-        gstd::msg::reply(Event::URI { value, token }, 0).expect("Failed to reply Event::URI");
+        self.emit_event(Event::URI { value, token }, &[]);
+    }
+    fn emit_permanent_uri_event(&self, value: String, token: u128) {
+        self.emit_event(Event::PermanentURI { value, token }, &[]);
     }
 }
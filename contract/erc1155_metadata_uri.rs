@@ -4,7 +4,26 @@ use crate::*;
 
 /// ERC1155MetadataURI interface
 impl<T: IConfig> IERC1155MetadataURI<T> for Contract<T> {
-    fn uri(&self, _token: T::TokenId) -> T::Text {
-        self.base_uri.clone()
+    // both the EIP-1155 `{id}` and the older `{}` placeholders are replaced
+    fn uri(&self, token: T::TokenId) -> T::Text {
+        let template = self.token_uris.get(&token).unwrap_or(&self.base_uri);
+        let id = format!("{:064x}", token);
+        template
+            .to_string()
+            .replace("{id}", &id)
+            .replace("{}", &id)
+            .into()
+    }
+    fn set_token_uri(&mut self, token: T::TokenId, uri: Option<T::Text>) -> Result<(), Error> {
+        self.check_set_token_uri(token, uri.clone())?;
+        match uri {
+            Some(u) => {
+                self.token_uris.insert(token, u);
+            }
+            None => {
+                self.token_uris.remove_entry(&token);
+            }
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,20 @@
+//! contract implementation
+
+use crate::*;
+
+/// ERC1155ReceiverRegistry interface
+impl<T: IConfig> IERC1155ReceiverRegistry<T> for Contract<T> {
+    fn is_receiver(&self, who: T::AccountId) -> bool {
+        self.receivers.contains(&who)
+    }
+    fn register_receiver(&mut self, who: T::AccountId) -> Result<(), Error> {
+        self.check_register_receiver(who)?;
+        self.receivers.insert(who);
+        Ok(())
+    }
+    fn unregister_receiver(&mut self, who: T::AccountId) -> Result<(), Error> {
+        self.check_unregister_receiver(who)?;
+        self.receivers.remove(&who);
+        Ok(())
+    }
+}
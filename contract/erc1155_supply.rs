@@ -0,0 +1,13 @@
+//! contract implementation
+
+use crate::*;
+
+/// ERC1155Supply interface
+impl<T: IConfig> IERC1155Supply<T> for Contract<T> {
+    fn total_supply(&self, token: T::TokenId) -> T::Balance {
+        *self.total_supply.get(&token).unwrap_or(&T::Balance::zero())
+    }
+    fn exists(&self, token: T::TokenId) -> bool {
+        !self.total_supply(token).is_zero()
+    }
+}
@@ -0,0 +1,20 @@
+//! contract implementation
+
+use crate::*;
+
+/// EventSubscription interface
+impl<T: IConfig> IEventSubscription<T> for Contract<T> {
+    fn is_subscriber(&self, who: T::AccountId) -> bool {
+        self.subscribers.contains(&who)
+    }
+    fn subscribe(&mut self, who: T::AccountId) -> Result<(), Error> {
+        self.check_subscribe(who)?;
+        self.subscribers.insert(who);
+        Ok(())
+    }
+    fn unsubscribe(&mut self, who: T::AccountId) -> Result<(), Error> {
+        self.check_unsubscribe(who)?;
+        self.subscribers.remove(&who);
+        Ok(())
+    }
+}
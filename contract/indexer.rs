@@ -0,0 +1,47 @@
+//! contract implementation
+
+use crate::*;
+
+/// Indexer interface
+impl<T: IConfig> IIndexer<T> for Contract<T> {
+    fn all_balances(&self) -> Vec<(T::TokenId, T::AccountId, T::Balance)> {
+        self.balances
+            .iter()
+            .flat_map(|(token, kv)| kv.iter().map(move |(owner, v)| (*token, *owner, *v)))
+            .filter(|(_, _, v)| !v.is_zero())
+            .collect()
+    }
+    fn balances_of_owner(&self, owner: T::AccountId) -> Vec<(T::TokenId, T::Balance)> {
+        self.tokens_of(owner, 0, u32::MAX)
+    }
+    fn all_holders_of(&self, token: T::TokenId) -> Vec<(T::AccountId, T::Balance)> {
+        self.holders_of(token, 0, u32::MAX)
+    }
+    fn all_approvals(&self, owner: T::AccountId) -> Vec<T::AccountId> {
+        self.approvals
+            .get(&owner)
+            .into_iter()
+            .flatten()
+            .filter(|(_, approved)| **approved)
+            .map(|(operator, _)| *operator)
+            .collect()
+    }
+    fn all_token_metadata(&self, offset: u32, limit: u32) -> Vec<(T::TokenId, TokenMetadata)> {
+        self.metadata_registry
+            .iter()
+            .map(|(token, metadata)| (*token, metadata.clone()))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+    fn stats(&self) -> Stats {
+        Stats {
+            tokens: self.total_supply.values().filter(|v| !v.is_zero()).count() as u32,
+            holders: self
+                .owned_tokens
+                .keys()
+                .filter(|owner| **owner != self.ctx.program_id())
+                .count() as u32,
+        }
+    }
+}
@@ -4,7 +4,13 @@
 
 use gstd::{collections::*, prelude::*, ActorId};
 use interface::*;
-use io::TokenMetadata;
+use io::{
+    Error, Listing, MintPolicy, Role, Royalty, Signature, SnapshotEntry, Stats, TokenMetadata,
+    VestingSchedule,
+};
+
+#[cfg(test)]
+mod contract_invariant_test;
 
 #[cfg(test)]
 mod contract_panic_test;
@@ -12,24 +18,74 @@ mod contract_panic_test;
 #[cfg(test)]
 mod contract_test;
 
+mod access_control;
 mod erc1155;
+mod erc1155_allowance;
 mod erc1155_check;
+mod erc1155_enumerable;
 mod erc1155_ext;
 mod erc1155_gear_ext;
 mod erc1155_metadata_uri;
+mod erc1155_receiver_registry;
+mod erc1155_supply;
+mod event_subscription;
+mod indexer;
+mod marketplace;
+mod meta_transaction;
+mod mint_policy;
+mod pausable;
+mod permit;
+mod primary_sale;
+mod royalty;
+mod snapshot;
 mod token_metadata_registry;
+mod versioned_state;
+mod vesting;
+
+pub use versioned_state::*;
+
+/// allowances granted by a single owner, keyed by (operator, token)
+pub type Allowances<T> =
+    BTreeMap<(<T as IConfig>::AccountId, <T as IConfig>::TokenId), <T as IConfig>::Balance>;
 
 /// Contract struct
+///
+/// the encoding is the latest layout of `VersionedState`, a field change
+/// needs a new version and a migration
 #[derive(Default, Clone, Encode, Decode, TypeInfo)]
 pub struct Contract<T: IConfig> {
+    /// execution context, not part of the state
+    #[codec(skip)]
     pub ctx: T,
     pub owner: T::AccountId,
+    pub roles: BTreeMap<T::AccountId, BTreeSet<Role>>,
     pub name: T::Text,
     pub symbol: T::Text,
     pub base_uri: T::Text,
     pub balances: BTreeMap<T::TokenId, BTreeMap<T::AccountId, T::Balance>>,
+    pub owned_tokens: BTreeMap<T::AccountId, BTreeSet<T::TokenId>>,
+    pub total_supply: BTreeMap<T::TokenId, T::Balance>,
+    pub mint_policies: BTreeMap<T::TokenId, MintPolicy<T::Balance>>,
     pub approvals: BTreeMap<T::AccountId, BTreeMap<T::AccountId, bool>>,
+    pub allowances: BTreeMap<T::AccountId, Allowances<T>>,
+    pub nonces: BTreeMap<T::AccountId, u64>,
     pub metadata_registry: BTreeMap<T::TokenId, TokenMetadata>,
+    pub token_uris: BTreeMap<T::TokenId, T::Text>,
+    /// first minter of each token
+    pub creators: BTreeMap<T::TokenId, T::AccountId>,
+    pub frozen_metadata: BTreeSet<T::TokenId>,
+    pub prices: BTreeMap<T::TokenId, T::Balance>,
+    pub proceeds: T::Balance,
+    pub listings: BTreeMap<u64, ListingOf<T>>,
+    pub next_listing_id: u64,
+    pub default_royalty: Option<Royalty<T::AccountId>>,
+    pub token_royalties: BTreeMap<T::TokenId, Royalty<T::AccountId>>,
+    pub vesting_schedules: BTreeMap<T::AccountId, Vec<VestingScheduleOf<T>>>,
+    pub import_checksum: [u8; 32],
+    pub receivers: BTreeSet<T::AccountId>,
+    pub subscribers: BTreeSet<T::AccountId>,
+    pub paused: bool,
+    pub paused_tokens: BTreeSet<T::TokenId>,
 }
 
 /// constructor method
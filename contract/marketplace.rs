@@ -0,0 +1,71 @@
+//! contract implementation
+
+use crate::*;
+
+/// Marketplace interface
+impl<T: IConfig> IMarketplace<T> for Contract<T> {
+    fn listing(&self, listing_id: u64) -> Option<ListingOf<T>> {
+        self.listings.get(&listing_id).cloned()
+    }
+    fn listings(&self, token: T::TokenId, offset: u32, limit: u32) -> Vec<(u64, ListingOf<T>)> {
+        self.listings
+            .iter()
+            .filter(|(_, listing)| listing.token == token)
+            .map(|(id, listing)| (*id, listing.clone()))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+    fn list(
+        &mut self,
+        token: T::TokenId,
+        amount: T::Balance,
+        price: T::Balance,
+    ) -> Result<u64, Error> {
+        self.check_list(token, amount, price)?;
+        let seller = self.sender();
+        self.move_balance(seller, self.ctx.program_id(), token, amount);
+        let listing_id = self.next_listing_id;
+        self.next_listing_id = listing_id.saturating_add(1);
+        self.listings.insert(
+            listing_id,
+            Listing {
+                seller,
+                token,
+                amount,
+                price,
+            },
+        );
+        Ok(listing_id)
+    }
+    fn cancel(&mut self, listing_id: u64) -> Result<(), Error> {
+        self.check_cancel(listing_id)?;
+        if let Some(listing) = self.listings.remove(&listing_id) {
+            self.move_balance(
+                self.ctx.program_id(),
+                listing.seller,
+                listing.token,
+                listing.amount,
+            );
+        }
+        Ok(())
+    }
+    fn buy_listing(&mut self, listing_id: u64, amount: T::Balance) -> Result<SaleOf<T>, Error> {
+        self.check_buy_listing(listing_id, amount)?;
+        let listing = self
+            .listings
+            .get_mut(&listing_id)
+            .ok_or(Error::NoSuchListing)?;
+        // can't overflow, see check_buy_listing
+        let cost = listing.price.checked_mul(&amount).unwrap_or_default();
+        listing.amount = listing.amount.saturating_sub(&amount);
+        let (seller, token) = (listing.seller, listing.token);
+        if listing.amount.is_zero() {
+            self.listings.remove(&listing_id);
+        }
+        self.move_balance(self.ctx.program_id(), self.sender(), token, amount);
+        // can't underflow, see check_buy_listing
+        let refund = self.ctx.value() - cost;
+        Ok((seller, cost, refund))
+    }
+}
@@ -0,0 +1,56 @@
+//! contract implementation
+
+use crate::*;
+use permit::blake2_256;
+
+/// tag hashed into the domain of signed actions, distinct from the permit
+/// one so that the two kinds of signed messages never collide
+const EXECUTE_DOMAIN: &[u8] = b"gm/execute-signed";
+
+/// MetaTransaction interface
+impl<T: IConfig> IMetaTransaction<T> for Contract<T> {
+    fn signed_message(
+        &self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+    ) -> [u8; 32] {
+        let domain = blake2_256(&(EXECUTE_DOMAIN, self.ctx.program_id()).encode());
+        blake2_256(&(domain, signer, nonce, deadline, payload).encode())
+    }
+    fn enter_signed(
+        &mut self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+        signature: Signature,
+    ) -> Result<(), Error> {
+        self.check_execute_signed(signer, nonce, deadline, payload, signature)?;
+        self.nonces.insert(signer, nonce.saturating_add(1));
+        self.ctx.set_signer(Some(signer));
+        Ok(())
+    }
+    fn exit_signed(&mut self, signer: T::AccountId, nonce: u64, succeeded: bool) {
+        self.ctx.set_signer(None);
+        // a failed action leaves the state untouched, nonce included
+        if !succeeded && self.nonce(signer) == nonce.saturating_add(1) {
+            self.nonces.insert(signer, nonce);
+        }
+    }
+    fn execute_signed<R>(
+        &mut self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+        signature: Signature,
+        f: impl FnOnce(&mut Self) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        self.enter_signed(signer, nonce, deadline, payload, signature)?;
+        let result = f(self);
+        self.exit_signed(signer, nonce, result.is_ok());
+        result
+    }
+}
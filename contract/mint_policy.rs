@@ -0,0 +1,19 @@
+//! contract implementation
+
+use crate::*;
+
+/// IMintPolicy interface
+impl<T: IConfig> IMintPolicy<T> for Contract<T> {
+    fn mint_policy(&self, token: T::TokenId) -> MintPolicy<T::Balance> {
+        self.mint_policies.get(&token).copied().unwrap_or_default()
+    }
+    fn create_token(
+        &mut self,
+        token: T::TokenId,
+        policy: MintPolicy<T::Balance>,
+    ) -> Result<(), Error> {
+        self.check_create_token(token, policy)?;
+        self.mint_policies.insert(token, policy);
+        Ok(())
+    }
+}
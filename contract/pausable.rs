@@ -0,0 +1,33 @@
+//! contract implementation
+
+use crate::*;
+
+/// Pausable interface
+impl<T: IConfig> IPausable<T> for Contract<T> {
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+    fn is_token_paused(&self, token: T::TokenId) -> bool {
+        self.paused_tokens.contains(&token)
+    }
+    fn pause(&mut self) -> Result<(), Error> {
+        self.check_pause()?;
+        self.paused = true;
+        Ok(())
+    }
+    fn unpause(&mut self) -> Result<(), Error> {
+        self.check_unpause()?;
+        self.paused = false;
+        Ok(())
+    }
+    fn pause_token(&mut self, token: T::TokenId) -> Result<(), Error> {
+        self.check_pause_token(token)?;
+        self.paused_tokens.insert(token);
+        Ok(())
+    }
+    fn unpause_token(&mut self, token: T::TokenId) -> Result<(), Error> {
+        self.check_unpause_token(token)?;
+        self.paused_tokens.remove(&token);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! contract implementation
+
+use crate::*;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+
+/// tag hashed into the domain separator
+const PERMIT_DOMAIN: &[u8] = b"gm/permit-for-all";
+
+pub(crate) fn blake2_256(data: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(data).into()
+}
+
+/// Permit interface
+impl<T: IConfig> IPermit<T> for Contract<T> {
+    fn nonce(&self, owner: T::AccountId) -> u64 {
+        *self.nonces.get(&owner).unwrap_or(&0)
+    }
+    fn domain_separator(&self) -> [u8; 32] {
+        blake2_256(&(PERMIT_DOMAIN, self.ctx.program_id()).encode())
+    }
+    fn permit_message(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+        deadline: u64,
+        nonce: u64,
+    ) -> [u8; 32] {
+        blake2_256(
+            &(
+                self.domain_separator(),
+                owner,
+                operator,
+                approved,
+                deadline,
+                nonce,
+            )
+                .encode(),
+        )
+    }
+    fn permit_for_all(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+        deadline: u64,
+        nonce: u64,
+        signature: Signature,
+    ) -> Result<(), Error> {
+        self.check_permit_for_all(owner, operator, approved, deadline, nonce, signature)?;
+        self.nonces.insert(owner, nonce.saturating_add(1));
+        self.set_approval_for_all_unchecked(owner, operator, approved);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! contract implementation
+
+use crate::*;
+
+/// PrimarySale interface
+impl<T: IConfig> IPrimarySale<T> for Contract<T> {
+    fn price(&self, token: T::TokenId) -> Option<T::Balance> {
+        self.prices.get(&token).copied()
+    }
+    fn proceeds(&self) -> T::Balance {
+        self.proceeds
+    }
+    fn set_price(&mut self, token: T::TokenId, price: Option<T::Balance>) -> Result<(), Error> {
+        self.check_set_price(token, price)?;
+        match price {
+            Some(price) => self.prices.insert(token, price),
+            None => self.prices.remove(&token),
+        };
+        Ok(())
+    }
+    fn buy(
+        &mut self,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(T::Balance, T::Balance), Error> {
+        self.check_buy(token, amount)?;
+        // can't overflow, see check_buy
+        let cost = self
+            .price(token)
+            .and_then(|price| price.checked_mul(&amount))
+            .unwrap_or_default();
+        // tokens sold by the contract are created by its owner
+        self.record_creator(token, self.owner);
+        self.mint_unchecked(self.sender(), token, amount);
+        self.proceeds = self
+            .proceeds
+            .checked_add(&cost)
+            .expect("proceeds overflow, see check_buy");
+        // can't underflow, see check_buy
+        let refund = self.ctx.value() - cost;
+        Ok((cost, refund))
+    }
+    fn withdraw(&mut self) -> Result<T::Balance, Error> {
+        self.check_withdraw()?;
+        Ok(mem::take(&mut self.proceeds))
+    }
+}
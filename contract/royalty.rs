@@ -0,0 +1,66 @@
+//! contract implementation
+
+use crate::*;
+
+/// basis points of the whole sale price
+const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Royalty interface
+impl<T: IConfig> IRoyalty<T> for Contract<T> {
+    fn default_royalty(&self) -> Option<Royalty<T::AccountId>> {
+        self.default_royalty
+    }
+    fn token_royalty(&self, token: T::TokenId) -> Option<Royalty<T::AccountId>> {
+        self.token_royalties.get(&token).copied()
+    }
+    fn royalty_info(
+        &self,
+        token: T::TokenId,
+        sale_price: T::Balance,
+    ) -> (T::AccountId, T::Balance) {
+        let Some(royalty) = self.token_royalty(token).or(self.default_royalty) else {
+            return Default::default();
+        };
+        let basis_points = T::Balance::from(royalty.basis_points);
+        let max = T::Balance::from(MAX_BASIS_POINTS);
+        // divide first when the product doesn't fit, the remainder keeps the
+        // result exact
+        let amount = match sale_price.checked_mul(&basis_points) {
+            Some(product) => product / max,
+            None => sale_price / max * basis_points + sale_price % max * basis_points / max,
+        };
+        (royalty.receiver, amount)
+    }
+    fn set_default_royalty(&mut self, royalty: Option<Royalty<T::AccountId>>) -> Result<(), Error> {
+        self.check_set_default_royalty(royalty)?;
+        self.default_royalty = royalty;
+        Ok(())
+    }
+    fn set_token_royalty(
+        &mut self,
+        token: T::TokenId,
+        royalty: Option<Royalty<T::AccountId>>,
+    ) -> Result<(), Error> {
+        self.check_set_token_royalty(token, royalty)?;
+        match royalty {
+            Some(royalty) => self.token_royalties.insert(token, royalty),
+            None => self.token_royalties.remove(&token),
+        };
+        Ok(())
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    pub(crate) fn check_royalty(royalty: Option<Royalty<T::AccountId>>) -> Result<(), Error> {
+        let Some(royalty) = royalty else {
+            return Ok(());
+        };
+        if royalty.receiver == T::AccountId::default() {
+            return Err(Error::ZeroAddress);
+        }
+        if royalty.basis_points > MAX_BASIS_POINTS {
+            return Err(Error::RoyaltyTooHigh);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+//! contract implementation
+
+use crate::*;
+use permit::blake2_256;
+
+/// Snapshot interface
+impl<T: IConfig> ISnapshot<T> for Contract<T> {
+    fn snapshot(&self, cursor: u32, limit: u32) -> (Vec<SnapshotEntryOf<T>>, Option<u32>) {
+        let mut entries = self.snapshot_entries().skip(cursor as usize);
+        let chunk: Vec<_> = entries.by_ref().take(limit as usize).collect();
+        let next = entries
+            .next()
+            .map(|_| cursor.saturating_add(chunk.len() as u32));
+        (chunk, next)
+    }
+    fn snapshot_checksum(&self) -> [u8; 32] {
+        self.snapshot_entries().fold([0; 32], |checksum, entry| {
+            Self::chain_checksum(checksum, &entry)
+        })
+    }
+    fn import_checksum(&self) -> [u8; 32] {
+        self.import_checksum
+    }
+    fn import_snapshot(&mut self, entries: Vec<SnapshotEntryOf<T>>) -> Result<[u8; 32], Error> {
+        self.check_import_snapshot(&entries)?;
+        for entry in entries {
+            self.import_checksum = Self::chain_checksum(self.import_checksum, &entry);
+            match entry {
+                SnapshotEntry::Balance {
+                    token,
+                    owner,
+                    amount,
+                } => self.mint_unchecked(owner, token, amount),
+                SnapshotEntry::Approval { owner, operator } => {
+                    self.set_approval_for_all_unchecked(owner, operator, true)
+                }
+                SnapshotEntry::Allowance {
+                    owner,
+                    operator,
+                    token,
+                    amount,
+                } => {
+                    self.allowances
+                        .entry(owner)
+                        .or_default()
+                        .insert((operator, token), amount);
+                }
+                SnapshotEntry::Policy { token, policy } => {
+                    self.mint_policies.insert(token, policy);
+                }
+                SnapshotEntry::Metadata { token, metadata } => {
+                    self.metadata_registry.insert(token, *metadata);
+                }
+                SnapshotEntry::TokenUri { token, uri } => {
+                    self.token_uris.insert(token, uri);
+                }
+                SnapshotEntry::Creator { token, creator } => {
+                    self.creators.insert(token, creator);
+                }
+                SnapshotEntry::Frozen { token } => {
+                    self.frozen_metadata.insert(token);
+                }
+                SnapshotEntry::Listing {
+                    listing_id,
+                    listing,
+                } => {
+                    self.mint_unchecked(self.ctx.program_id(), listing.token, listing.amount);
+                    self.next_listing_id = self.next_listing_id.max(listing_id.saturating_add(1));
+                    self.listings.insert(listing_id, listing);
+                }
+                SnapshotEntry::VestingSchedule { owner, schedule } => {
+                    self.vesting_schedules
+                        .entry(owner)
+                        .or_default()
+                        .push(schedule);
+                }
+                SnapshotEntry::Role { account, role } => {
+                    self.roles.entry(account).or_default().insert(role);
+                }
+                SnapshotEntry::DefaultRoyalty { royalty } => {
+                    self.default_royalty = Some(royalty);
+                }
+                SnapshotEntry::TokenRoyalty { token, royalty } => {
+                    self.token_royalties.insert(token, royalty);
+                }
+                SnapshotEntry::Price { token, price } => {
+                    self.prices.insert(token, price);
+                }
+            }
+        }
+        Ok(self.import_checksum)
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    /// entries of the snapshot in a fixed order
+    pub(crate) fn snapshot_entries(&self) -> impl Iterator<Item = SnapshotEntryOf<T>> + '_ {
+        // escrowed units travel with their listings, the importing program
+        // escrows them under its own id
+        let program_id = self.ctx.program_id();
+        let mut escrowed = BTreeMap::new();
+        for listing in self.listings.values() {
+            let amount = escrowed
+                .entry(listing.token)
+                .or_insert_with(T::Balance::zero);
+            *amount = amount.saturating_add(&listing.amount);
+        }
+        let balances = self.balances.iter().flat_map(move |(token, kv)| {
+            let escrowed = escrowed
+                .get(token)
+                .copied()
+                .unwrap_or_else(T::Balance::zero);
+            kv.iter()
+                .map(move |(owner, amount)| {
+                    if *owner == program_id {
+                        (owner, amount.saturating_sub(&escrowed))
+                    } else {
+                        (owner, *amount)
+                    }
+                })
+                .filter(|(_, amount)| !amount.is_zero())
+                .map(|(owner, amount)| SnapshotEntry::Balance {
+                    token: *token,
+                    owner: *owner,
+                    amount,
+                })
+        });
+        let approvals = self.approvals.iter().flat_map(|(owner, operators)| {
+            operators
+                .iter()
+                .filter(|(_, approved)| **approved)
+                .map(|(operator, _)| SnapshotEntry::Approval {
+                    owner: *owner,
+                    operator: *operator,
+                })
+        });
+        let allowances = self.allowances.iter().flat_map(|(owner, allowances)| {
+            allowances
+                .iter()
+                .map(|((operator, token), amount)| SnapshotEntry::Allowance {
+                    owner: *owner,
+                    operator: *operator,
+                    token: *token,
+                    amount: *amount,
+                })
+        });
+        let policies = self
+            .mint_policies
+            .iter()
+            .map(|(token, policy)| SnapshotEntry::Policy {
+                token: *token,
+                policy: *policy,
+            });
+        let metadata =
+            self.metadata_registry
+                .iter()
+                .map(|(token, metadata)| SnapshotEntry::Metadata {
+                    token: *token,
+                    metadata: Box::new(metadata.clone()),
+                });
+        let uris = self
+            .token_uris
+            .iter()
+            .map(|(token, uri)| SnapshotEntry::TokenUri {
+                token: *token,
+                uri: uri.clone(),
+            });
+        let creators = self
+            .creators
+            .iter()
+            .map(|(token, creator)| SnapshotEntry::Creator {
+                token: *token,
+                creator: *creator,
+            });
+        let frozen = self
+            .frozen_metadata
+            .iter()
+            .map(|token| SnapshotEntry::Frozen { token: *token });
+        let listings = self
+            .listings
+            .iter()
+            .map(|(listing_id, listing)| SnapshotEntry::Listing {
+                listing_id: *listing_id,
+                listing: listing.clone(),
+            });
+        let schedules = self
+            .vesting_schedules
+            .iter()
+            .flat_map(|(owner, schedules)| {
+                schedules
+                    .iter()
+                    .map(|schedule| SnapshotEntry::VestingSchedule {
+                        owner: *owner,
+                        schedule: *schedule,
+                    })
+            });
+        let roles = self.roles.iter().flat_map(|(account, roles)| {
+            roles.iter().map(|role| SnapshotEntry::Role {
+                account: *account,
+                role: *role,
+            })
+        });
+        let default_royalty = self
+            .default_royalty
+            .map(|royalty| SnapshotEntry::DefaultRoyalty { royalty });
+        let token_royalties =
+            self.token_royalties
+                .iter()
+                .map(|(token, royalty)| SnapshotEntry::TokenRoyalty {
+                    token: *token,
+                    royalty: *royalty,
+                });
+        let prices = self
+            .prices
+            .iter()
+            .map(|(token, price)| SnapshotEntry::Price {
+                token: *token,
+                price: *price,
+            });
+        balances
+            .chain(approvals)
+            .chain(allowances)
+            .chain(policies)
+            .chain(metadata)
+            .chain(uris)
+            .chain(creators)
+            .chain(frozen)
+            .chain(listings)
+            .chain(schedules)
+            .chain(roles)
+            .chain(default_royalty)
+            .chain(token_royalties)
+            .chain(prices)
+    }
+    /// hash of the checksum so far followed by the entry
+    pub(crate) fn chain_checksum(checksum: [u8; 32], entry: &SnapshotEntryOf<T>) -> [u8; 32] {
+        blake2_256(&(checksum, entry).encode())
+    }
+}
@@ -7,8 +7,12 @@ impl<T: IConfig> ITokenMetadataRegistry<T> for Contract<T> {
     fn get_token_metadata(&self, token: T::TokenId) -> Option<TokenMetadata> {
         self.metadata_registry.get(&token).cloned()
     }
-    fn update_token_metadata(&mut self, token: T::TokenId, metadata: Option<TokenMetadata>) {
-        self.check_update_token_metadata(token, metadata.clone());
+    fn update_token_metadata(
+        &mut self,
+        token: T::TokenId,
+        metadata: Option<TokenMetadata>,
+    ) -> Result<(), Error> {
+        self.check_update_token_metadata(token, metadata.clone())?;
         match metadata {
             Some(m) => {
                 self.metadata_registry.insert(token, m);
@@ -17,5 +21,17 @@ impl<T: IConfig> ITokenMetadataRegistry<T> for Contract<T> {
                 self.metadata_registry.remove_entry(&token);
             }
         }
+        Ok(())
+    }
+    fn creator(&self, token: T::TokenId) -> Option<T::AccountId> {
+        self.creators.get(&token).copied()
+    }
+    fn is_metadata_frozen(&self, token: T::TokenId) -> bool {
+        self.frozen_metadata.contains(&token)
+    }
+    fn freeze_metadata(&mut self, token: T::TokenId) -> Result<(), Error> {
+        self.check_freeze_metadata(token)?;
+        self.frozen_metadata.insert(token);
+        Ok(())
     }
 }
@@ -0,0 +1,120 @@
+//! contract implementation
+
+use crate::*;
+
+/// version of the `Contract` layout
+pub const STATE_VERSION: u32 = 2;
+
+/// Versioned interface
+impl<T: IConfig> IVersioned for Contract<T> {
+    fn version(&self) -> u32 {
+        STATE_VERSION
+    }
+}
+
+/// contract state tagged with the version of its layout
+///
+/// older layouts are frozen copies of `Contract` and are migrated one
+/// version at a time by `into_latest`
+#[derive(Clone, Encode, Decode, TypeInfo)]
+pub enum VersionedState<T: IConfig> {
+    #[codec(index = 1)]
+    V1(StateV1<T>),
+    #[codec(index = 2)]
+    V2(Box<Contract<T>>),
+}
+
+impl<T: IConfig> VersionedState<T> {
+    pub fn version(&self) -> u32 {
+        match self {
+            Self::V1(_) => 1,
+            Self::V2(_) => 2,
+        }
+    }
+    /// migrates the state to the latest layout
+    pub fn into_latest(self) -> Contract<T> {
+        match self {
+            Self::V1(state) => state.migrate(),
+            Self::V2(state) => *state,
+        }
+    }
+}
+
+impl<T: IConfig> From<Contract<T>> for VersionedState<T> {
+    fn from(state: Contract<T>) -> Self {
+        Self::V2(Box::new(state))
+    }
+}
+
+/// token metadata of the first layout
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct TokenMetadataV1 {
+    pub name: String,
+    pub description: String,
+    pub image_uri: String,
+    pub json_uri: String,
+}
+
+impl From<TokenMetadataV1> for TokenMetadata {
+    fn from(metadata: TokenMetadataV1) -> Self {
+        Self {
+            name: metadata.name,
+            description: metadata.description,
+            image_uri: metadata.image_uri,
+            json_uri: metadata.json_uri,
+            ..Default::default()
+        }
+    }
+}
+
+/// first layout, before roles, supplies, mint policies and the extensions
+#[derive(Clone, Default, Encode, Decode, TypeInfo)]
+pub struct StateV1<T: IConfig> {
+    pub owner: T::AccountId,
+    pub name: T::Text,
+    pub symbol: T::Text,
+    pub base_uri: T::Text,
+    pub balances: BTreeMap<T::TokenId, BTreeMap<T::AccountId, T::Balance>>,
+    pub approvals: BTreeMap<T::AccountId, BTreeMap<T::AccountId, bool>>,
+    pub metadata_registry: BTreeMap<T::TokenId, TokenMetadataV1>,
+}
+
+impl<T: IConfig> StateV1<T> {
+    /// tokens minted so far keep the default `Fixed` policy and have no
+    /// creator, supplies and owned tokens are derived from the balances
+    pub fn migrate(self) -> Contract<T> {
+        let balances: BTreeMap<_, BTreeMap<_, _>> = self
+            .balances
+            .into_iter()
+            .map(|(token, kv)| {
+                let kv = kv.into_iter().filter(|(_, v)| !v.is_zero()).collect();
+                (token, kv)
+            })
+            .collect();
+        let mut owned_tokens: BTreeMap<T::AccountId, BTreeSet<T::TokenId>> = BTreeMap::new();
+        let mut total_supply = BTreeMap::new();
+        for (token, kv) in &balances {
+            for (owner, amount) in kv {
+                owned_tokens.entry(*owner).or_default().insert(*token);
+                let supply = total_supply.entry(*token).or_insert_with(T::Balance::zero);
+                *supply = supply.saturating_add(amount);
+            }
+        }
+        Contract {
+            owner: self.owner,
+            name: self.name,
+            symbol: self.symbol,
+            base_uri: self.base_uri,
+            balances,
+            owned_tokens,
+            total_supply,
+            approvals: self.approvals,
+            metadata_registry: self
+                .metadata_registry
+                .into_iter()
+                .map(|(token, metadata)| (token, metadata.into()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! contract implementation
+
+use crate::*;
+
+/// Vesting interface
+impl<T: IConfig> IVesting<T> for Contract<T> {
+    fn vesting_schedules(&self, owner: T::AccountId) -> Vec<VestingScheduleOf<T>> {
+        self.vesting_schedules
+            .get(&owner)
+            .cloned()
+            .unwrap_or_default()
+    }
+    fn locked_balance_of(&self, owner: T::AccountId, token: T::TokenId) -> T::Balance {
+        self.vesting_schedules(owner)
+            .iter()
+            .filter(|schedule| schedule.token == token)
+            .fold(T::Balance::zero(), |locked, schedule| {
+                locked.saturating_add(&schedule.amount.saturating_sub(&schedule.released))
+            })
+    }
+    fn releasable(&self, owner: T::AccountId, token: T::TokenId) -> T::Balance {
+        let now = self.ctx.block_timestamp();
+        self.vesting_schedules(owner)
+            .iter()
+            .filter(|schedule| schedule.token == token)
+            .fold(T::Balance::zero(), |releasable, schedule| {
+                let vested = Self::vested_amount(schedule, now);
+                releasable.saturating_add(&vested.saturating_sub(&schedule.released))
+            })
+    }
+    fn mint_vested(
+        &mut self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<(), Error> {
+        self.check_mint_vested(to, token, amount, start, cliff, duration)?;
+        self.record_creator(token, self.sender());
+        self.mint_unchecked(to, token, amount);
+        self.vesting_schedules
+            .entry(to)
+            .or_default()
+            .push(VestingSchedule {
+                token,
+                amount,
+                released: T::Balance::zero(),
+                start,
+                cliff,
+                duration,
+            });
+        Ok(())
+    }
+    fn release(&mut self, token: T::TokenId) -> Result<T::Balance, Error> {
+        self.check_release(token)?;
+        let owner = self.sender();
+        let now = self.ctx.block_timestamp();
+        let mut released = T::Balance::zero();
+        if let Some(schedules) = self.vesting_schedules.get_mut(&owner) {
+            for schedule in schedules.iter_mut().filter(|s| s.token == token) {
+                let vested = Self::vested_amount(schedule, now);
+                released = released.saturating_add(&vested.saturating_sub(&schedule.released));
+                schedule.released = vested;
+            }
+            schedules.retain(|schedule| schedule.released < schedule.amount);
+            if schedules.is_empty() {
+                self.vesting_schedules.remove(&owner);
+            }
+        }
+        Ok(released)
+    }
+}
+
+impl<T: IConfig> Contract<T> {
+    /// units of the schedule vested at `now`
+    pub(crate) fn vested_amount(schedule: &VestingScheduleOf<T>, now: u64) -> T::Balance {
+        let elapsed = now.saturating_sub(schedule.start);
+        if elapsed < schedule.cliff {
+            return T::Balance::zero();
+        }
+        if elapsed >= schedule.duration {
+            return schedule.amount;
+        }
+        // scale both times down so that they fit the u32 conversion, and
+        // further while the product overflows
+        let mut shift = 32u32.saturating_sub(schedule.duration.leading_zeros());
+        loop {
+            let elapsed = T::Balance::from((elapsed >> shift) as u32);
+            let duration = T::Balance::from((schedule.duration >> shift) as u32);
+            let whole = schedule.amount / duration;
+            let rest = schedule.amount % duration;
+            // can't overflow, the result is below the amount
+            if let Some(rest) = rest.checked_mul(&elapsed) {
+                return whole * elapsed + rest / duration;
+            }
+            shift += 1;
+        }
+    }
+}
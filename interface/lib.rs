@@ -2,8 +2,14 @@
 #![no_std]
 
 use gstd::{prelude::*, ActorId};
-use io::TokenMetadata;
+use io::{
+    Error, Listing, MintPolicy, Role, Royalty, Signature, SnapshotEntry, Stats, TokenMetadata,
+    VestingSchedule,
+};
 
+/// sanity checks performed before any state mutation
+///
+/// a failed check returns the reason as an `Error` and leaves the state untouched
 pub trait IERC1155Check<T: IConfig> {
     fn check_transfer_from(
         &self,
@@ -11,26 +17,128 @@ pub trait IERC1155Check<T: IConfig> {
         to: T::AccountId,
         token: T::TokenId,
         amount: T::Balance,
-    );
+    ) -> Result<(), Error>;
     fn check_batch_transfer_from(
         &self,
         from: T::AccountId,
         to: T::AccountId,
         token: Vec<T::TokenId>,
         amount: Vec<T::Balance>,
-    );
-    fn check_balance_of_batch(&self, who: Vec<T::AccountId>, token: Vec<T::TokenId>);
-    fn check_mint(&self, to: T::AccountId, token: T::TokenId, amount: T::Balance);
-    fn check_mint_batch(&self, to: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>);
+    ) -> Result<(), Error>;
+    fn check_balance_of_batch(
+        &self,
+        who: Vec<T::AccountId>,
+        token: Vec<T::TokenId>,
+    ) -> Result<(), Error>;
+    fn check_mint(
+        &self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error>;
+    fn check_mint_batch(
+        &self,
+        to: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error>;
     fn check_set_approval_for_all(
         &self,
         owner: T::AccountId,
         operator: T::AccountId,
         approved: bool,
-    );
-    fn check_burn(&self, from: T::AccountId, token: T::TokenId, amount: T::Balance);
-    fn check_burn_batch(&self, from: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>);
-    fn check_update_token_metadata(&self, token: T::TokenId, metadata: Option<TokenMetadata>);
+    ) -> Result<(), Error>;
+    fn check_approve(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error>;
+    fn check_permit_for_all(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+        deadline: u64,
+        nonce: u64,
+        signature: Signature,
+    ) -> Result<(), Error>;
+    fn check_execute_signed(
+        &self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+        signature: Signature,
+    ) -> Result<(), Error>;
+    fn check_set_price(&self, token: T::TokenId, price: Option<T::Balance>) -> Result<(), Error>;
+    fn check_buy(&self, token: T::TokenId, amount: T::Balance) -> Result<(), Error>;
+    fn check_withdraw(&self) -> Result<(), Error>;
+    fn check_list(
+        &self,
+        token: T::TokenId,
+        amount: T::Balance,
+        price: T::Balance,
+    ) -> Result<(), Error>;
+    fn check_cancel(&self, listing_id: u64) -> Result<(), Error>;
+    fn check_buy_listing(&self, listing_id: u64, amount: T::Balance) -> Result<(), Error>;
+    fn check_mint_vested(
+        &self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<(), Error>;
+    fn check_release(&self, token: T::TokenId) -> Result<(), Error>;
+    fn check_import_snapshot(&self, entries: &[SnapshotEntryOf<T>]) -> Result<(), Error>;
+    fn check_set_default_royalty(
+        &self,
+        royalty: Option<Royalty<T::AccountId>>,
+    ) -> Result<(), Error>;
+    fn check_set_token_royalty(
+        &self,
+        token: T::TokenId,
+        royalty: Option<Royalty<T::AccountId>>,
+    ) -> Result<(), Error>;
+    fn check_burn(
+        &self,
+        from: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error>;
+    fn check_burn_batch(
+        &self,
+        from: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error>;
+    fn check_update_token_metadata(
+        &self,
+        token: T::TokenId,
+        metadata: Option<TokenMetadata>,
+    ) -> Result<(), Error>;
+    fn check_set_token_uri(&self, token: T::TokenId, uri: Option<T::Text>) -> Result<(), Error>;
+    fn check_freeze_metadata(&self, token: T::TokenId) -> Result<(), Error>;
+    fn check_grant_role(&self, account: T::AccountId, role: Role) -> Result<(), Error>;
+    fn check_revoke_role(&self, account: T::AccountId, role: Role) -> Result<(), Error>;
+    fn check_renounce_role(&self, account: T::AccountId, role: Role) -> Result<(), Error>;
+    fn check_transfer_ownership(&self, new_owner: T::AccountId) -> Result<(), Error>;
+    fn check_create_token(
+        &self,
+        token: T::TokenId,
+        policy: MintPolicy<T::Balance>,
+    ) -> Result<(), Error>;
+    fn check_register_receiver(&self, who: T::AccountId) -> Result<(), Error>;
+    fn check_unregister_receiver(&self, who: T::AccountId) -> Result<(), Error>;
+    fn check_pause(&self) -> Result<(), Error>;
+    fn check_unpause(&self) -> Result<(), Error>;
+    fn check_pause_token(&self, token: T::TokenId) -> Result<(), Error>;
+    fn check_unpause_token(&self, token: T::TokenId) -> Result<(), Error>;
+    fn check_subscribe(&self, who: T::AccountId) -> Result<(), Error>;
+    fn check_unsubscribe(&self, who: T::AccountId) -> Result<(), Error>;
 }
 
 /// ERC1155 interface gear extension
@@ -52,31 +160,422 @@ pub trait IERC1155GearExt {
         amount: Vec<u128>,
     );
     fn emit_approval_for_all_event(&self, owner: ActorId, spender: ActorId, approved: bool);
+    fn emit_approval_event(&self, owner: ActorId, operator: ActorId, token: u128, amount: u128);
     fn emit_uri_event(&self, value: String, token: u128);
+    fn emit_permanent_uri_event(&self, value: String, token: u128);
     /// whoami is a utility method for emitting an event containing sender and origin of the current tx
     fn emit_whoami_event(&self);
     fn emit_update_token_metadata_event(&self, token: u128, metadata: Option<TokenMetadata>);
+    fn emit_role_granted_event(&self, account: ActorId, role: Role, sender: ActorId);
+    fn emit_role_revoked_event(&self, account: ActorId, role: Role, sender: ActorId);
+    fn emit_ownership_transferred_event(&self, previous_owner: ActorId, new_owner: ActorId);
+    fn emit_token_created_event(&self, token: u128, policy: MintPolicy);
+    fn emit_receiver_registered_event(&self, receiver: ActorId);
+    fn emit_receiver_unregistered_event(&self, receiver: ActorId);
+    fn emit_paused_event(&self, account: ActorId);
+    fn emit_unpaused_event(&self, account: ActorId);
+    fn emit_token_paused_event(&self, token: u128, account: ActorId);
+    fn emit_token_unpaused_event(&self, token: u128, account: ActorId);
+    fn emit_price_set_event(&self, token: u128, price: Option<u128>);
+    /// refunds `refund` of the attached value along with the event
+    fn emit_purchased_event(
+        &self,
+        buyer: ActorId,
+        token: u128,
+        amount: u128,
+        cost: u128,
+        refund: u128,
+    );
+    /// pays `amount` to the owner along with the event
+    fn emit_withdrawn_event(&self, to: ActorId, amount: u128);
+    fn emit_released_event(&self, owner: ActorId, token: u128, amount: u128);
+    fn emit_snapshot_imported_event(&self, entries: u32, checksum: [u8; 32]);
+    fn emit_listed_event(
+        &self,
+        listing_id: u64,
+        seller: ActorId,
+        token: u128,
+        amount: u128,
+        price: u128,
+    );
+    /// pays `cost` to the seller and refunds `refund` to the buyer along
+    /// with the event
+    fn emit_sold_event(
+        &self,
+        listing_id: u64,
+        buyer: ActorId,
+        seller: ActorId,
+        amount: u128,
+        cost: u128,
+        refund: u128,
+    );
+    fn emit_cancelled_event(&self, listing_id: u64);
+    fn emit_default_royalty_set_event(&self, royalty: Option<Royalty>);
+    fn emit_token_royalty_set_event(&self, token: u128, royalty: Option<Royalty>);
+    fn emit_subscribed_event(&self, subscriber: ActorId);
+    fn emit_unsubscribed_event(&self, subscriber: ActorId);
+    fn emit_error_event(&self, error: Error);
 }
 
 /// ERC1155 interface extension
 pub trait IERC1155Ext<T: IConfig>: IERC1155<T> {
     fn name(&self) -> T::Text;
     fn symbol(&self) -> T::Text;
-    fn burn(&mut self, from: T::AccountId, token: T::TokenId, amount: T::Balance);
-    fn burn_batch(&mut self, from: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>);
-    fn mint(&mut self, to: T::AccountId, token: T::TokenId, amount: T::Balance);
-    fn mint_batch(&mut self, to: T::AccountId, token: Vec<T::TokenId>, amount: Vec<T::Balance>);
+    fn burn(
+        &mut self,
+        from: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error>;
+    fn burn_batch(
+        &mut self,
+        from: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error>;
+    fn mint(
+        &mut self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error>;
+    fn mint_batch(
+        &mut self,
+        to: T::AccountId,
+        token: Vec<T::TokenId>,
+        amount: Vec<T::Balance>,
+    ) -> Result<(), Error>;
+}
+
+/// ERC1155 enumeration extension
+///
+/// pages through existing tokens, their holders and the tokens held by an
+/// account, skipping zero balances
+pub trait IERC1155Enumerable<T: IConfig> {
+    /// ids of tokens with a non-zero total supply
+    fn tokens(&self, offset: u32, limit: u32) -> Vec<T::TokenId>;
+    /// holders of the token with their balances
+    fn holders_of(
+        &self,
+        token: T::TokenId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(T::AccountId, T::Balance)>;
+    /// tokens held by the account with their balances
+    fn tokens_of(
+        &self,
+        owner: T::AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(T::TokenId, T::Balance)>;
+}
+
+/// signed approvals
+///
+/// lets a relayer submit `set_approval_for_all` on behalf of an owner who
+/// signed the permit message off-chain. each permit carries the owner's
+/// current nonce and a deadline in block timestamp milliseconds
+pub trait IPermit<T: IConfig> {
+    /// nonce the next permit of the owner must carry
+    fn nonce(&self, owner: T::AccountId) -> u64;
+    /// hash binding permits to this program
+    fn domain_separator(&self) -> [u8; 32];
+    /// hash the owner signs to authorize a permit
+    fn permit_message(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+        deadline: u64,
+        nonce: u64,
+    ) -> [u8; 32];
+    fn permit_for_all(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+        deadline: u64,
+        nonce: u64,
+        signature: Signature,
+    ) -> Result<(), Error>;
+}
+
+/// meta-transactions
+///
+/// lets a relayer submit actions signed off-chain, which are performed with
+/// the signer as sender. signed actions share the nonce of permits
+pub trait IMetaTransaction<T: IConfig> {
+    /// hash the signer signs to authorize the encoded action `payload`
+    fn signed_message(
+        &self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+    ) -> [u8; 32];
+    /// verifies the signature, consumes the nonce and makes the signer the
+    /// sender until `exit_signed`
+    fn enter_signed(
+        &mut self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+        signature: Signature,
+    ) -> Result<(), Error>;
+    /// resets the sender, the nonce is handed back if the action failed and
+    /// no later nonce of the signer was consumed meanwhile
+    fn exit_signed(&mut self, signer: T::AccountId, nonce: u64, succeeded: bool);
+    /// runs `f` between `enter_signed` and `exit_signed`
+    fn execute_signed<R>(
+        &mut self,
+        signer: T::AccountId,
+        nonce: u64,
+        deadline: u64,
+        payload: &[u8],
+        signature: Signature,
+        f: impl FnOnce(&mut Self) -> Result<R, Error>,
+    ) -> Result<R, Error>;
+}
+
+/// primary sale
+///
+/// the owner prices token ids, buyers mint them by attaching at least
+/// `price * amount` of value and the owner withdraws the proceeds
+pub trait IPrimarySale<T: IConfig> {
+    /// price of a single token, `None` if not for sale
+    fn price(&self, token: T::TokenId) -> Option<T::Balance>;
+    /// proceeds available for withdrawal
+    fn proceeds(&self) -> T::Balance;
+    /// puts the token on sale, `None` stops the sale
+    fn set_price(&mut self, token: T::TokenId, price: Option<T::Balance>) -> Result<(), Error>;
+    /// mints to the sender and returns the cost and the rest of the attached
+    /// value, to be refunded
+    fn buy(
+        &mut self,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(T::Balance, T::Balance), Error>;
+    /// resets the proceeds and returns the amount to be paid to the owner
+    fn withdraw(&mut self) -> Result<T::Balance, Error>;
+}
+
+/// bulk reads for indexers
+///
+/// unlike `IERC1155Enumerable` these return everything at once and are
+/// meant for metawasm reads rather than on-chain queries. balances include
+/// the units escrowed by listings under the program id, so that they add up
+/// to the total supply
+pub trait IIndexer<T: IConfig> {
+    /// every non-zero balance as (token, owner, balance)
+    fn all_balances(&self) -> Vec<(T::TokenId, T::AccountId, T::Balance)>;
+    fn balances_of_owner(&self, owner: T::AccountId) -> Vec<(T::TokenId, T::Balance)>;
+    fn all_holders_of(&self, token: T::TokenId) -> Vec<(T::AccountId, T::Balance)>;
+    /// operators approved for all tokens of the owner
+    fn all_approvals(&self, owner: T::AccountId) -> Vec<T::AccountId>;
+    fn all_token_metadata(&self, offset: u32, limit: u32) -> Vec<(T::TokenId, TokenMetadata)>;
+    /// counters of the collection, the program itself isn't a holder
+    fn stats(&self) -> Stats;
+}
+
+/// version of the state layout, see `VersionedState`
+pub trait IVersioned {
+    fn version(&self) -> u32;
+}
+
+/// state snapshot for migrating to a new deployment
+///
+/// the snapshot lists balances, approvals, allowances, mint policies,
+/// metadata, listings, vesting schedules, roles, royalties and prices in a
+/// fixed order, its checksum chains the hashes of all entries
+pub trait ISnapshot<T: IConfig> {
+    /// entries from `cursor` on and the cursor of the next chunk
+    fn snapshot(&self, cursor: u32, limit: u32) -> (Vec<SnapshotEntryOf<T>>, Option<u32>);
+    fn snapshot_checksum(&self) -> [u8; 32];
+    /// checksum of the entries imported so far
+    fn import_checksum(&self) -> [u8; 32];
+    /// applies the entries and returns the updated import checksum
+    fn import_snapshot(&mut self, entries: Vec<SnapshotEntryOf<T>>) -> Result<[u8; 32], Error>;
+}
+
+/// vesting
+///
+/// vested units are part of the balance but can't be transferred, burned or
+/// listed until unlocked with `release`
+pub trait IVesting<T: IConfig> {
+    fn vesting_schedules(&self, owner: T::AccountId) -> Vec<VestingScheduleOf<T>>;
+    /// units of the token not yet released
+    fn locked_balance_of(&self, owner: T::AccountId, token: T::TokenId) -> T::Balance;
+    /// units of the token vested by now but not yet released
+    fn releasable(&self, owner: T::AccountId, token: T::TokenId) -> T::Balance;
+    fn mint_vested(
+        &mut self,
+        to: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    ) -> Result<(), Error>;
+    /// unlocks the releasable units of the sender and returns their amount
+    fn release(&mut self, token: T::TokenId) -> Result<T::Balance, Error>;
+}
+
+/// royalty info, as in EIP-2981
+///
+/// a token royalty overrides the default royalty of the collection
+pub trait IRoyalty<T: IConfig> {
+    fn default_royalty(&self) -> Option<Royalty<T::AccountId>>;
+    fn token_royalty(&self, token: T::TokenId) -> Option<Royalty<T::AccountId>>;
+    /// receiver and amount of the royalty owed on a sale at `sale_price`,
+    /// the zero account and amount if there is none
+    fn royalty_info(&self, token: T::TokenId, sale_price: T::Balance)
+        -> (T::AccountId, T::Balance);
+    fn set_default_royalty(&mut self, royalty: Option<Royalty<T::AccountId>>) -> Result<(), Error>;
+    fn set_token_royalty(
+        &mut self,
+        token: T::TokenId,
+        royalty: Option<Royalty<T::AccountId>>,
+    ) -> Result<(), Error>;
+}
+
+/// fixed-price marketplace
+///
+/// listed units are escrowed in the balance of this program until they are
+/// sold or the listing is cancelled
+pub trait IMarketplace<T: IConfig> {
+    fn listing(&self, listing_id: u64) -> Option<ListingOf<T>>;
+    /// listings of the token, oldest first
+    fn listings(&self, token: T::TokenId, offset: u32, limit: u32) -> Vec<(u64, ListingOf<T>)>;
+    /// escrows the sender's units and returns the listing id
+    fn list(
+        &mut self,
+        token: T::TokenId,
+        amount: T::Balance,
+        price: T::Balance,
+    ) -> Result<u64, Error>;
+    /// returns the units left to the seller
+    fn cancel(&mut self, listing_id: u64) -> Result<(), Error>;
+    /// moves units to the sender and returns the seller, the cost owed to
+    /// them and the rest of the attached value, to be refunded
+    fn buy_listing(&mut self, listing_id: u64, amount: T::Balance) -> Result<SaleOf<T>, Error>;
+}
+
+/// ERC1155 allowance extension
+///
+/// lets an owner approve an operator to move a limited amount of a single
+/// token, consumed by transfers and burns of operators not approved for all
+pub trait IERC1155Allowance<T: IConfig> {
+    fn allowance(
+        &self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        token: T::TokenId,
+    ) -> T::Balance;
+    /// sets the allowance, 0 revokes it
+    fn approve(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        token: T::TokenId,
+        amount: T::Balance,
+    ) -> Result<(), Error>;
 }
 
 /// ERC1155MetadataURI interface definition
 // https://eips.ethereum.org/EIPS/eip-1155#metadata
 pub trait IERC1155MetadataURI<T: IConfig> {
+    /// returns the per-token uri override or the base uri, with `{id}` replaced
+    /// by the lowercase hex token id padded to 64 characters
     fn uri(&self, token: T::TokenId) -> T::Text;
+    /// sets or removes (None) the per-token uri override
+    fn set_token_uri(&mut self, token: T::TokenId, uri: Option<T::Text>) -> Result<(), Error>;
+}
+
+/// ERC1155Supply interface extension
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC1155/extensions/ERC1155Supply.sol
+pub trait IERC1155Supply<T: IConfig> {
+    /// total amount of tokens in existence with the given id
+    fn total_supply(&self, token: T::TokenId) -> T::Balance;
+    /// whether any token with the given id is in existence
+    fn exists(&self, token: T::TokenId) -> bool;
 }
 
+/// access control interface
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/access/AccessControl.sol
+pub trait IAccessControl<T: IConfig> {
+    fn owner(&self) -> T::AccountId;
+    /// the owner implicitly holds every role
+    fn has_role(&self, account: T::AccountId, role: Role) -> bool;
+    fn grant_role(&mut self, account: T::AccountId, role: Role) -> Result<(), Error>;
+    fn revoke_role(&mut self, account: T::AccountId, role: Role) -> Result<(), Error>;
+    /// lets an account give up a role it holds, account must be the sender
+    fn renounce_role(&mut self, account: T::AccountId, role: Role) -> Result<(), Error>;
+    fn transfer_ownership(&mut self, new_owner: T::AccountId) -> Result<(), Error>;
+}
+
+/// mint policy interface
+///
+/// lets a token id opt into repeated minting before it is first minted
+pub trait IMintPolicy<T: IConfig> {
+    /// returns `MintPolicy::Fixed` for tokens that were never created
+    fn mint_policy(&self, token: T::TokenId) -> MintPolicy<T::Balance>;
+    fn create_token(
+        &mut self,
+        token: T::TokenId,
+        policy: MintPolicy<T::Balance>,
+    ) -> Result<(), Error>;
+}
+
+/// ERC1155 receiver registry
+///
+/// gear can't tell programs and users apart, so programs that implement the
+/// receiver protocol register themselves to be notified of incoming tokens
+pub trait IERC1155ReceiverRegistry<T: IConfig> {
+    fn is_receiver(&self, who: T::AccountId) -> bool;
+    fn register_receiver(&mut self, who: T::AccountId) -> Result<(), Error>;
+    fn unregister_receiver(&mut self, who: T::AccountId) -> Result<(), Error>;
+}
+
+/// emergency stop
+///
+/// while the contract or a token is paused, transfers, mints, burns and
+/// metadata updates of the affected tokens fail. only the owner and accounts
+/// with the pauser role may pause or unpause
+pub trait IPausable<T: IConfig> {
+    fn is_paused(&self) -> bool;
+    fn is_token_paused(&self, token: T::TokenId) -> bool;
+    fn pause(&mut self) -> Result<(), Error>;
+    fn unpause(&mut self) -> Result<(), Error>;
+    fn pause_token(&mut self, token: T::TokenId) -> Result<(), Error>;
+    fn unpause_token(&mut self, token: T::TokenId) -> Result<(), Error>;
+}
+
+/// event subscriptions
+///
+/// subscribers are sent a copy of every event emitted by a successful
+/// transaction, in addition to the reply to the caller
+pub trait IEventSubscription<T: IConfig> {
+    fn is_subscriber(&self, who: T::AccountId) -> bool;
+    fn subscribe(&mut self, who: T::AccountId) -> Result<(), Error>;
+    fn unsubscribe(&mut self, who: T::AccountId) -> Result<(), Error>;
+}
+
+/// token metadata registry
+///
+/// the first minter of a token is its creator, only the creator and accounts
+/// with the metadata admin role may update its metadata and uri, until they
+/// are frozen
 pub trait ITokenMetadataRegistry<T: IConfig> {
     fn get_token_metadata(&self, token: T::TokenId) -> Option<TokenMetadata>;
-    fn update_token_metadata(&mut self, token: T::TokenId, metadata: Option<TokenMetadata>);
+    fn update_token_metadata(
+        &mut self,
+        token: T::TokenId,
+        metadata: Option<TokenMetadata>,
+    ) -> Result<(), Error>;
+    fn creator(&self, token: T::TokenId) -> Option<T::AccountId>;
+    fn is_metadata_frozen(&self, token: T::TokenId) -> bool;
+    fn freeze_metadata(&mut self, token: T::TokenId) -> Result<(), Error>;
 }
 
 /// ERC1155 interface definition
@@ -85,22 +584,31 @@ pub trait ITokenMetadataRegistry<T: IConfig> {
 // https://github.com/paritytech/ink/blob/master/examples/erc1155/lib.rs
 pub trait IERC1155<T: IConfig>: IERC1155Check<T> {
     fn balance_of(&self, who: T::AccountId, token: T::TokenId) -> T::Balance;
-    fn balance_of_batch(&self, who: Vec<T::AccountId>, token: Vec<T::TokenId>) -> Vec<T::Balance>;
+    fn balance_of_batch(
+        &self,
+        who: Vec<T::AccountId>,
+        token: Vec<T::TokenId>,
+    ) -> Result<Vec<T::Balance>, Error>;
     fn safe_transfer_from(
         &mut self,
         from: T::AccountId,
         to: T::AccountId,
         token: T::TokenId,
         amount: T::Balance,
-    );
+    ) -> Result<(), Error>;
     fn safe_batch_transfer_from(
         &mut self,
         from: T::AccountId,
         to: T::AccountId,
         token: Vec<T::TokenId>,
         amount: Vec<T::Balance>,
-    );
-    fn set_approval_for_all(&mut self, owner: T::AccountId, operator: T::AccountId, approved: bool);
+    ) -> Result<(), Error>;
+    fn set_approval_for_all(
+        &mut self,
+        owner: T::AccountId,
+        operator: T::AccountId,
+        approved: bool,
+    ) -> Result<(), Error>;
     fn is_approved_for_all(&self, owner: T::AccountId, operator: T::AccountId) -> bool;
 }
 
@@ -113,15 +621,56 @@ pub trait IConfig: Default {
     type Text: IText;
     type TokenId: ITokenId;
     fn sender(&self) -> Self::AccountId;
+    /// account that started the chain of messages leading to this one
+    fn origin(&self) -> Self::AccountId;
+    /// makes `sender` resolve to the signer of a meta-transaction until it
+    /// is reset with `None`
+    fn set_signer(&mut self, signer: Option<Self::AccountId>);
+    /// value attached to the current message
+    fn value(&self) -> Self::Balance;
+    /// id of the running program
+    fn program_id(&self) -> Self::AccountId;
+    /// timestamp of the current block in milliseconds
+    fn block_timestamp(&self) -> u64;
+    /// height of the current block
+    fn block_height(&self) -> u32;
+    /// whether `signature` over `message` was made by the key of `signer`
+    fn verify(&self, signer: Self::AccountId, message: &[u8], signature: Signature) -> bool;
 }
 
+/// listing with the account, token and balance types of the config
+pub type ListingOf<T> =
+    Listing<<T as IConfig>::AccountId, <T as IConfig>::TokenId, <T as IConfig>::Balance>;
+
+/// snapshot entry with the types of the config
+pub type SnapshotEntryOf<T> = SnapshotEntry<
+    <T as IConfig>::AccountId,
+    <T as IConfig>::TokenId,
+    <T as IConfig>::Balance,
+    <T as IConfig>::Text,
+>;
+
+/// seller, cost and refund of a listing purchase
+pub type SaleOf<T> = (
+    <T as IConfig>::AccountId,
+    <T as IConfig>::Balance,
+    <T as IConfig>::Balance,
+);
+
+/// vesting schedule with the token and balance types of the config
+pub type VestingScheduleOf<T> = VestingSchedule<<T as IConfig>::TokenId, <T as IConfig>::Balance>;
+
 /// token id trait alias
-pub trait ITokenId = Eq + Copy + Clone + core::hash::Hash + Ord + fmt::Debug + Default;
+///
+/// LowerHex is required for `{id}` substitution in token uris.
+pub trait ITokenId =
+    Eq + Copy + Clone + core::hash::Hash + Ord + fmt::Debug + fmt::LowerHex + Default + Encode;
 
 /// account id trait alias
 ///
 /// a method for returning the zero address is required.
-pub trait IAccountId = Eq + Copy + Clone + core::hash::Hash + Ord + fmt::Debug + Default;
+/// Encode is required for hashing account ids into permit messages.
+pub trait IAccountId = Eq + Copy + Clone + core::hash::Hash + Ord + fmt::Debug + Default + Encode;
 
 /// account balance trait alias
 ///
@@ -130,6 +679,7 @@ pub trait IBalance = num_traits::Zero
     + num_traits::One
     + num_traits::CheckedAdd
     + num_traits::CheckedSub
+    + num_traits::CheckedMul
     + num_traits::SaturatingAdd
     + num_traits::SaturatingSub
     + num_traits::sign::Unsigned
@@ -139,9 +689,11 @@ pub trait IBalance = num_traits::Zero
     + PartialOrd
     + Default
     + From<u16>
-    + From<u32>;
+    + From<u32>
+    + Encode;
 
 /// text trait
 ///
 /// default value should be an empty string
-pub trait IText = From<&'static str> + Clone + fmt::Debug + fmt::Display + Default;
+pub trait IText =
+    From<&'static str> + From<String> + Clone + fmt::Debug + fmt::Display + Default + Encode;
@@ -11,6 +11,226 @@ pub struct TokenMetadata {
     pub description: String,
     pub image_uri: String,
     pub json_uri: String,
+    /// (trait_type, value) pairs
+    pub attributes: Vec<(String, String)>,
+    pub animation_url: String,
+    pub external_url: String,
+    /// decimals of a fungible token, 0 for NFTs
+    pub decimals: u8,
+    /// hash of the json at `json_uri`, e.g. blake2-256, so that clients can
+    /// tell if it has been swapped
+    pub content_hash: Option<[u8; 32]>,
+}
+
+/// access control roles
+///
+/// the contract owner implicitly holds every role
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Role {
+    /// allowed to mint tokens
+    Minter,
+    /// allowed to update metadata of any token
+    MetadataAdmin,
+    /// allowed to pause and unpause the contract
+    Pauser,
+}
+
+/// mint policy of a token id
+///
+/// the policy is chosen with `CreateToken` before the first mint, tokens
+/// minted without being created are `Fixed`
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MintPolicy<Balance = u128> {
+    /// can only be minted once, e.g. NFTs
+    #[default]
+    Fixed,
+    /// can be minted again as long as total supply stays within max supply
+    Capped { max_supply: Balance },
+    /// can be minted again without limit, e.g. in-game currencies
+    Unlimited,
+}
+
+/// royalty owed to `receiver` on secondary sales, as in EIP-2981
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Copy)]
+pub struct Royalty<AccountId = ActorId> {
+    pub receiver: AccountId,
+    /// share of the sale price in hundredths of a percent
+    pub basis_points: u16,
+}
+
+/// units of a token unlocking linearly over time
+///
+/// nothing unlocks before `start + cliff`, everything is unlocked at
+/// `start + duration`, times are block timestamps in milliseconds
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Copy)]
+pub struct VestingSchedule<TokenId = u128, Balance = u128> {
+    pub token: TokenId,
+    pub amount: Balance,
+    /// units already unlocked with `Release`
+    pub released: Balance,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+/// entry of a state snapshot, used to migrate balances, approvals,
+/// metadata, listings, vesting schedules, roles, royalties and prices to a
+/// new deployment
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone)]
+pub enum SnapshotEntry<AccountId = ActorId, TokenId = u128, Balance = u128, Text = String> {
+    Balance {
+        token: TokenId,
+        owner: AccountId,
+        amount: Balance,
+    },
+    Approval {
+        owner: AccountId,
+        operator: AccountId,
+    },
+    Allowance {
+        owner: AccountId,
+        operator: AccountId,
+        token: TokenId,
+        amount: Balance,
+    },
+    Policy {
+        token: TokenId,
+        policy: MintPolicy<Balance>,
+    },
+    Metadata {
+        token: TokenId,
+        metadata: Box<TokenMetadata>,
+    },
+    TokenUri {
+        token: TokenId,
+        uri: Text,
+    },
+    Creator {
+        token: TokenId,
+        creator: AccountId,
+    },
+    Frozen {
+        token: TokenId,
+    },
+    /// open listing, its units are escrowed again by the importing program
+    Listing {
+        listing_id: u64,
+        listing: Listing<AccountId, TokenId, Balance>,
+    },
+    VestingSchedule {
+        owner: AccountId,
+        schedule: VestingSchedule<TokenId, Balance>,
+    },
+    Role {
+        account: AccountId,
+        role: Role,
+    },
+    DefaultRoyalty {
+        royalty: Royalty<AccountId>,
+    },
+    TokenRoyalty {
+        token: TokenId,
+        royalty: Royalty<AccountId>,
+    },
+    Price {
+        token: TokenId,
+        price: Balance,
+    },
+}
+
+/// collection counters for indexers
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Stats {
+    /// tokens with a non-zero total supply
+    pub tokens: u32,
+    /// accounts with a non-zero balance of any token, escrow excluded
+    pub holders: u32,
+}
+
+/// fixed-price listing of escrowed units
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone)]
+pub struct Listing<AccountId = ActorId, TokenId = u128, Balance = u128> {
+    pub seller: AccountId,
+    pub token: TokenId,
+    /// units left for sale
+    pub amount: Balance,
+    /// price of a single unit
+    pub price: Balance,
+}
+
+/// signature over a permit or signed action, made with the key behind the signer's account id
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Copy)]
+pub enum Signature {
+    Sr25519([u8; 64]),
+    Ed25519([u8; 64]),
+}
+
+/// reasons for a failed check, replied as `Event::Error`
+#[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Copy)]
+pub enum Error {
+    /// cannot transfer, mint or burn 0 amount
+    ZeroAmount,
+    /// cannot transfer, mint or grant to the zero (black hole) address
+    ZeroAddress,
+    /// sender is neither the account owner nor an approved operator
+    NeedsApproval,
+    /// sender's per-token allowance is lower than the amount
+    InsufficientAllowance,
+    InsufficientBalance,
+    SelfTransfer,
+    /// token and amount (or account) vectors differ in length
+    LengthMismatch,
+    /// token with fixed mint policy is already minted
+    AlreadyMinted,
+    MaxSupplyExceeded,
+    TokenAlreadyExists,
+    NoSuchToken,
+    /// sender is not the account the operation is performed for
+    NotAccountOwner,
+    /// sender is not the contract owner
+    NotContractOwner,
+    /// sender lacks the role
+    MissingRole(Role),
+    /// receiver program rejected the tokens or failed to reply
+    ReceiverRejected,
+    /// contract is paused
+    Paused,
+    /// token is paused
+    TokenPaused,
+    /// contract or token is not paused
+    NotPaused,
+    /// deadline of the permit or signed action has passed
+    PermitExpired,
+    /// nonce is not the signer's current nonce
+    InvalidNonce,
+    /// signature doesn't match the signer
+    InvalidSignature,
+    /// token has no price set
+    NotForSale,
+    /// attached value doesn't cover the price
+    InsufficientValue,
+    /// value attached to an action other than `Buy` or `BuyListing`
+    NotPayable,
+    NoSuchListing,
+    /// royalty above 10000 basis points
+    RoyaltyTooHigh,
+    /// balance is sufficient but partly locked by vesting schedules
+    BalanceLocked,
+    /// cliff longer than the duration of a vesting schedule
+    InvalidSchedule,
+    /// a balance, total supply or proceeds would exceed the balance type
+    BalanceOverflow,
+    /// metadata field too long or out of range
+    InvalidMetadata,
+    /// sender is neither the creator of the token nor a metadata admin
+    NotTokenCreator,
+    /// metadata and uri of the token are frozen
+    MetadataFrozen,
+    /// subscriber set is full
+    TooManySubscribers,
+    /// balance of the contract itself holds listed units, only the
+    /// marketplace moves it
+    EscrowBalance,
 }
 
 /// contract I/O types for state queries and replies
@@ -23,8 +243,71 @@ pub mod query {
         BaseUri,
         BalanceOf(ActorId, u128),
         BalanceOfBatch(Vec<ActorId>, Vec<u128>),
-        IsApprovedForAll { owner: ActorId, operator: ActorId },
+        IsApprovedForAll {
+            owner: ActorId,
+            operator: ActorId,
+        },
         TokenMetadata(u128),
+        Uri(u128),
+        TotalSupply(u128),
+        Exists(u128),
+        Owner,
+        HasRole {
+            account: ActorId,
+            role: Role,
+        },
+        MintPolicy(u128),
+        Allowance {
+            owner: ActorId,
+            operator: ActorId,
+            token: u128,
+        },
+        IsPaused,
+        IsTokenPaused(u128),
+        IsMetadataFrozen(u128),
+        Nonce(ActorId),
+        DomainSeparator,
+        Price(u128),
+        Proceeds,
+        Listings {
+            token: u128,
+            offset: u32,
+            limit: u32,
+        },
+        RoyaltyInfo {
+            token: u128,
+            sale_price: u128,
+        },
+        Tokens {
+            offset: u32,
+            limit: u32,
+        },
+        HoldersOf {
+            token: u128,
+            offset: u32,
+            limit: u32,
+        },
+        TokensOf {
+            owner: ActorId,
+            offset: u32,
+            limit: u32,
+        },
+        LockedBalance {
+            owner: ActorId,
+            token: u128,
+        },
+        VestingSchedules(ActorId),
+        /// entries of the snapshot from `cursor` on
+        ///
+        /// the owner, proceeds, paused tokens, receivers and subscribers are
+        /// left out, the new deployment has its own owner who sets them again
+        Snapshot {
+            cursor: u32,
+            limit: u32,
+        },
+        SnapshotChecksum,
+        /// version of the state layout
+        Version,
     }
 
     #[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone)]
@@ -36,12 +319,55 @@ pub mod query {
         BalanceOfBatch(Vec<u128>),
         IsApprovedForAll(bool),
         TokenMetadata(Option<TokenMetadata>),
+        Uri(String),
+        TotalSupply(u128),
+        Exists(bool),
+        Owner(ActorId),
+        HasRole(bool),
+        MintPolicy(MintPolicy),
+        Allowance(u128),
+        IsPaused(bool),
+        IsTokenPaused(bool),
+        IsMetadataFrozen(bool),
+        Nonce(u64),
+        DomainSeparator([u8; 32]),
+        Price(Option<u128>),
+        Proceeds(u128),
+        Listings(Vec<(u64, Listing)>),
+        /// receiver and amount of the royalty, zero if there is none
+        RoyaltyInfo(ActorId, u128),
+        Tokens(Vec<u128>),
+        HoldersOf(Vec<(ActorId, u128)>),
+        TokensOf(Vec<(u128, u128)>),
+        LockedBalance(u128),
+        VestingSchedules(Vec<VestingSchedule>),
+        /// entries and the cursor of the next chunk, `None` after the last
+        Snapshot(Vec<SnapshotEntry>, Option<u32>),
+        SnapshotChecksum([u8; 32]),
+        Version(u32),
+        Error(Error),
     }
 }
 
 /// contract I/O types for transactions and events
 pub mod transaction {
     use super::*;
+    /// actions a signer can authorize off-chain, see `Action::ExecuteSigned`
+    #[derive(Debug, TypeInfo, Decode, Encode, PartialEq, Eq, Clone)]
+    pub enum SignedAction {
+        TransferFrom {
+            from: ActorId,
+            to: ActorId,
+            token: u128,
+            amount: u128,
+        },
+        BatchTransferFrom {
+            from: ActorId,
+            to: ActorId,
+            token: Vec<u128>,
+            amount: Vec<u128>,
+        },
+    }
     #[derive(Debug, TypeInfo, Decode, Encode, PartialEq, Eq, Clone)]
     pub enum Action {
         TransferFrom {
@@ -60,6 +386,30 @@ pub mod transaction {
             operator: ActorId,
             approved: bool,
         },
+        Approve {
+            operator: ActorId,
+            token: u128,
+            amount: u128,
+        },
+        /// `SetApprovalForAll` on behalf of `owner`, submitted by anyone
+        /// holding the owner's signature over the permit
+        PermitForAll {
+            owner: ActorId,
+            operator: ActorId,
+            approved: bool,
+            deadline: u64,
+            nonce: u64,
+            signature: Signature,
+        },
+        /// performs `action` with `signer` as sender, submitted by anyone
+        /// holding the signer's signature over the encoded action
+        ExecuteSigned {
+            action: SignedAction,
+            signer: ActorId,
+            nonce: u64,
+            deadline: u64,
+            signature: Signature,
+        },
         Mint {
             to: ActorId,
             token: u128,
@@ -84,6 +434,100 @@ pub mod transaction {
             token: u128,
             metadata: Option<TokenMetadata>,
         },
+        SetTokenUri {
+            token: u128,
+            uri: Option<String>,
+        },
+        /// makes the metadata and uri of the token permanently immutable
+        FreezeMetadata {
+            token: u128,
+        },
+        GrantRole {
+            account: ActorId,
+            role: Role,
+        },
+        RevokeRole {
+            account: ActorId,
+            role: Role,
+        },
+        RenounceRole {
+            role: Role,
+        },
+        TransferOwnership {
+            new_owner: ActorId,
+        },
+        CreateToken {
+            token: u128,
+            policy: MintPolicy,
+        },
+        RegisterReceiver,
+        UnregisterReceiver,
+        /// owner only, adds an actor sent a copy of every event
+        Subscribe {
+            subscriber: ActorId,
+        },
+        /// by the owner or the subscriber itself
+        Unsubscribe {
+            subscriber: ActorId,
+        },
+        Pause,
+        Unpause,
+        PauseToken {
+            token: u128,
+        },
+        UnpauseToken {
+            token: u128,
+        },
+        SetPrice {
+            token: u128,
+            price: Option<u128>,
+        },
+        /// payable, excess value is refunded
+        Buy {
+            token: u128,
+            amount: u128,
+        },
+        Withdraw,
+        /// mints units locked by a vesting schedule
+        MintVested {
+            to: ActorId,
+            token: u128,
+            amount: u128,
+            start: u64,
+            cliff: u64,
+            duration: u64,
+        },
+        /// unlocks the vested units of the sender
+        Release {
+            token: u128,
+        },
+        /// imports a chunk of the snapshot of another deployment while
+        /// paused, chunks are to be imported in order
+        ImportSnapshot(Vec<SnapshotEntry>),
+        /// moves `amount` of the sender's token into escrow for sale at
+        /// `price` per unit
+        List {
+            token: u128,
+            amount: u128,
+            price: u128,
+        },
+        Cancel {
+            listing_id: u64,
+        },
+        /// payable, excess value is refunded
+        BuyListing {
+            listing_id: u64,
+            amount: u128,
+        },
+        /// `None` removes the default royalty
+        SetDefaultRoyalty {
+            royalty: Option<Royalty>,
+        },
+        /// overrides the default royalty, `None` removes the override
+        SetTokenRoyalty {
+            token: u128,
+            royalty: Option<Royalty>,
+        },
         Whoami,
     }
     #[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone)]
@@ -107,6 +551,12 @@ pub mod transaction {
             operator: ActorId,
             approved: bool,
         },
+        Approval {
+            owner: ActorId,
+            operator: ActorId,
+            token: u128,
+            amount: u128,
+        },
         URI {
             value: String,
             token: u128,
@@ -115,10 +565,140 @@ pub mod transaction {
             token: u128,
             metadata: Option<TokenMetadata>,
         },
+        /// uri of the token won't change anymore
+        PermanentURI {
+            value: String,
+            token: u128,
+        },
+        RoleGranted {
+            account: ActorId,
+            role: Role,
+            sender: ActorId,
+        },
+        RoleRevoked {
+            account: ActorId,
+            role: Role,
+            sender: ActorId,
+        },
+        OwnershipTransferred {
+            previous_owner: ActorId,
+            new_owner: ActorId,
+        },
+        TokenCreated {
+            token: u128,
+            policy: MintPolicy,
+        },
+        ReceiverRegistered {
+            receiver: ActorId,
+        },
+        ReceiverUnregistered {
+            receiver: ActorId,
+        },
+        PriceSet {
+            token: u128,
+            price: Option<u128>,
+        },
+        Purchased {
+            buyer: ActorId,
+            token: u128,
+            amount: u128,
+            cost: u128,
+        },
+        Withdrawn {
+            to: ActorId,
+            amount: u128,
+        },
+        Released {
+            owner: ActorId,
+            token: u128,
+            amount: u128,
+        },
+        /// `checksum` covers every entry imported so far and matches
+        /// `Query::SnapshotChecksum` of the source once all are imported
+        SnapshotImported {
+            entries: u32,
+            checksum: [u8; 32],
+        },
+        Listed {
+            listing_id: u64,
+            seller: ActorId,
+            token: u128,
+            amount: u128,
+            price: u128,
+        },
+        Sold {
+            listing_id: u64,
+            buyer: ActorId,
+            seller: ActorId,
+            amount: u128,
+            cost: u128,
+        },
+        Cancelled {
+            listing_id: u64,
+        },
+        DefaultRoyaltySet {
+            royalty: Option<Royalty>,
+        },
+        TokenRoyaltySet {
+            token: u128,
+            royalty: Option<Royalty>,
+        },
+        Subscribed {
+            subscriber: ActorId,
+        },
+        Unsubscribed {
+            subscriber: ActorId,
+        },
+        Paused {
+            account: ActorId,
+        },
+        Unpaused {
+            account: ActorId,
+        },
+        TokenPaused {
+            token: u128,
+            account: ActorId,
+        },
+        TokenUnpaused {
+            token: u128,
+            account: ActorId,
+        },
+        Error(Error),
         Whoami {
             sender: ActorId,
+            origin: ActorId,
+            block_height: u32,
+            block_timestamp: u64,
+        },
+    }
+}
+
+/// I/O types for the ERC1155 receiver protocol
+///
+/// a registered receiver program is sent a `ReceiverAction` before it is
+/// credited any tokens, the transfer only happens if it replies `Accepted`
+pub mod receiver {
+    use super::*;
+    #[derive(Debug, TypeInfo, Decode, Encode, PartialEq, Eq, Clone)]
+    pub enum ReceiverAction {
+        OnERC1155Received {
+            operator: ActorId,
+            from: ActorId,
+            token: u128,
+            amount: u128,
+        },
+        OnERC1155BatchReceived {
+            operator: ActorId,
+            from: ActorId,
+            token: Vec<u128>,
+            amount: Vec<u128>,
         },
     }
+    #[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone)]
+    pub enum ReceiverReply {
+        Accepted,
+        Rejected,
+    }
 }
 
 /// contract I/O types for initialization
@@ -129,6 +709,8 @@ pub mod init {
         pub name: String,
         pub symbol: String,
         pub base_uri: String,
+        /// default royalty of the collection
+        pub royalty: Option<Royalty>,
     }
     #[derive(Debug, TypeInfo, Encode, Decode, PartialEq, Eq, Clone, Default)]
     pub struct InitOk;
@@ -136,4 +718,5 @@ pub mod init {
 
 pub use self::init::*;
 pub use self::query::*;
+pub use self::receiver::*;
 pub use self::transaction::*;
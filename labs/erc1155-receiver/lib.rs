@@ -0,0 +1,90 @@
+#![no_std]
+
+use gstd::{debug, msg, prelude::*, ActorId};
+use io::{Action, ReceiverAction, ReceiverReply};
+
+/// token ids this receiver refuses to accept, set during initialization
+static mut REJECTED: Vec<u128> = vec![];
+
+#[no_mangle]
+unsafe extern "C" fn handle() {
+    let action: ReceiverAction = msg::load().expect("Invalid handle message");
+    debug!("handle(): {:?}", action);
+    let accepted = match action {
+        ReceiverAction::OnERC1155Received { token, .. } => !REJECTED.contains(&token),
+        ReceiverAction::OnERC1155BatchReceived { token, .. } => {
+            token.iter().all(|tk| !REJECTED.contains(tk))
+        }
+    };
+    let reply = match accepted {
+        true => ReceiverReply::Accepted,
+        false => ReceiverReply::Rejected,
+    };
+    msg::reply(reply, 0).expect("Failed to reply");
+}
+
+/// initialized with the GM program to register with and the rejected token
+/// ids, GM only notifies programs that registered themselves
+#[no_mangle]
+unsafe extern "C" fn init() {
+    let (gm, rejected): (ActorId, Vec<u128>) = msg::load().expect("Invalid init message");
+    REJECTED = rejected;
+    debug!("init(gm = {:?}, REJECTED = {:?})", gm, REJECTED);
+    msg::send(gm, Action::RegisterReceiver, 0).expect("Failed to register with GM");
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use gstd::ActorId;
+    use gtest::{Log, Program, System};
+
+    #[test]
+    fn it_works() {
+        let system = System::new();
+        system.init_logger();
+
+        let program = Program::current(&system);
+
+        let gm = ActorId::from(100);
+        let res = program.send(42, (gm, vec![7u128]));
+        assert!(!res.main_failed());
+        let log = Log::builder()
+            .source(1)
+            .dest(100)
+            .payload(Action::RegisterReceiver);
+        assert!(res.contains(&log));
+
+        let res = program.send(
+            42,
+            ReceiverAction::OnERC1155Received {
+                operator: ActorId::from(42),
+                from: ActorId::zero(),
+                token: 1,
+                amount: 1,
+            },
+        );
+        let log = Log::builder()
+            .source(1)
+            .dest(42)
+            .payload(ReceiverReply::Accepted);
+        assert!(res.contains(&log));
+
+        let res = program.send(
+            42,
+            ReceiverAction::OnERC1155BatchReceived {
+                operator: ActorId::from(42),
+                from: ActorId::zero(),
+                token: vec![1, 7],
+                amount: vec![1, 1],
+            },
+        );
+        let log = Log::builder()
+            .source(1)
+            .dest(42)
+            .payload(ReceiverReply::Rejected);
+        assert!(res.contains(&log));
+    }
+}
@@ -2,6 +2,8 @@
 
 use crate::*;
 
+#[cfg(test)]
+use super::handle_test::init_receiver;
 #[cfg(test)]
 use gtest::{Program, System};
 
@@ -13,12 +15,13 @@ pub fn init_program(prog: &Program) {
             name: "gm".to_string(),
             symbol: "GM".to_string(),
             base_uri: "https://gm.dev/{}".to_string(),
+            royalty: None,
         },
     );
 }
 
 #[test]
-fn mint_twice_panics() {
+fn mint_twice_fails() {
     use super::*;
     let system = System::new();
     system.init_logger();
@@ -44,11 +47,14 @@ fn mint_twice_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::AlreadyMinted);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn mint_zero_panics() {
+fn mint_zero_fails() {
     use super::*;
     let system = System::new();
     system.init_logger();
@@ -65,11 +71,14 @@ fn mint_zero_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::ZeroAmount);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn burn_exceeding_balance_panics() {
+fn burn_exceeding_balance_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -85,11 +94,14 @@ fn burn_exceeding_balance_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::InsufficientBalance);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn burn_zero_panics() {
+fn burn_zero_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -105,11 +117,14 @@ fn burn_zero_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::ZeroAmount);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn transfer_zero_panics() {
+fn transfer_zero_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -126,11 +141,14 @@ fn transfer_zero_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::ZeroAmount);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn transfer_exceeding_balance_panics() {
+fn transfer_exceeding_balance_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -147,11 +165,14 @@ fn transfer_exceeding_balance_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::InsufficientBalance);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn transfer_from_non_owner_panics() {
+fn transfer_from_non_owner_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -177,11 +198,14 @@ fn transfer_from_non_owner_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::NeedsApproval);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn transfer_batch_length_mismatch_panics() {
+fn transfer_batch_length_mismatch_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -207,11 +231,14 @@ fn transfer_batch_length_mismatch_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::LengthMismatch);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
-fn burn_batch_length_mismatch_panics() {
+fn burn_batch_length_mismatch_fails() {
     let system = System::new();
     system.init_logger();
 
@@ -236,7 +263,10 @@ fn burn_batch_length_mismatch_panics() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::LengthMismatch);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
 }
 
 #[test]
@@ -266,5 +296,274 @@ fn transfer_batch_length_mismatch_works() {
         },
     );
 
-    assert!(res.main_failed());
+    let expected = Event::Error(Error::LengthMismatch);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn mint_from_non_minter_fails() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let res = program.send(
+        69,
+        Action::Mint {
+            to: ActorId::from(69),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::Error(Error::MissingRole(Role::Minter));
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn transfer_to_rejecting_receiver_fails() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+    init_receiver(&system, 100, ReceiverReply::Rejected, &program);
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let res = program.send(
+        42,
+        Action::TransferFrom {
+            from: ActorId::from(42),
+            to: ActorId::from(100),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::Error(Error::ReceiverRejected);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    // the rejected transfer left the balance untouched
+    let res = program.send(
+        42,
+        Action::TransferFrom {
+            from: ActorId::from(42),
+            to: ActorId::from(1),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::from(42),
+        to: ActorId::from(1),
+        token: 0,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn mint_to_rejecting_receiver_fails() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+    init_receiver(&system, 100, ReceiverReply::Rejected, &program);
+
+    let res = program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(100),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::Error(Error::ReceiverRejected);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn mint_when_paused_fails() {
+    use super::*;
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let res = program.send(42, Action::Pause);
+
+    let expected = Event::Paused {
+        account: ActorId::from(42),
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::Error(Error::Paused);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn mint_with_value_fails() {
+    use super::*;
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    system.mint_to(42, 1_000_000_000_000);
+
+    let res = program.send_with_value(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 1,
+        },
+        1_000_000_000_000,
+    );
+
+    let expected = Event::Error(Error::NotPayable);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn transfer_vested_fails() {
+    use super::*;
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    program.send(
+        42,
+        Action::MintVested {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 10,
+            start: u64::MAX / 2,
+            cliff: 0,
+            duration: 1000,
+        },
+    );
+    let res = program.send(
+        42,
+        Action::TransferFrom {
+            from: ActorId::from(42),
+            to: ActorId::from(7),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::Error(Error::BalanceLocked);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn execute_signed_failing_transfer_fails() {
+    use super::*;
+    use blake2::{digest::consts::U32, Blake2b, Digest};
+    use gtest::Log;
+
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let keypair = schnorrkel::MiniSecretKey::from_bytes(&[7; 32])
+        .unwrap()
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+    let signer = ActorId::from(keypair.public.to_bytes());
+    let domain: [u8; 32] =
+        Blake2b::<U32>::digest((b"gm/execute-signed".as_slice(), ActorId::from(1)).encode()).into();
+    let execute_signed = |amount: u128| {
+        let action = SignedAction::TransferFrom {
+            from: signer,
+            to: ActorId::from(7),
+            token: 0,
+            amount,
+        };
+        let message: [u8; 32] =
+            Blake2b::<U32>::digest((domain, signer, 0u64, u64::MAX, action.encode()).encode())
+                .into();
+        let signature = keypair.sign_simple(b"substrate", &message).to_bytes();
+        Action::ExecuteSigned {
+            action,
+            signer,
+            nonce: 0,
+            deadline: u64::MAX,
+            signature: Signature::Sr25519(signature),
+        }
+    };
+
+    program.send(
+        42,
+        Action::Mint {
+            to: signer,
+            token: 0,
+            amount: 1,
+        },
+    );
+    let res = program.send(42, execute_signed(2));
+
+    let expected = Event::Error(Error::InsufficientBalance);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    // the failed transfer left nonce 0 to the signer
+    let res = program.send(42, execute_signed(1));
+
+    let expected = Event::TransferSingle {
+        operator: signer,
+        from: signer,
+        to: ActorId::from(7),
+        token: 0,
+        amount: 1,
+    };
+
+    assert!(res.contains(&Log::builder().dest(42).payload(expected)));
 }
@@ -3,7 +3,7 @@
 use crate::*;
 
 #[cfg(test)]
-use gtest::{Program, System};
+use gtest::{Log, Program, System, WasmProgram};
 
 #[cfg(test)]
 pub fn init_program(prog: &Program) {
@@ -13,10 +13,42 @@ pub fn init_program(prog: &Program) {
             name: "gm".to_string(),
             symbol: "GM".to_string(),
             base_uri: "https://gm.dev/{}".to_string(),
+            royalty: None,
         },
     );
 }
 
+/// receiver program that replies to every receiver action with a fixed answer
+#[cfg(test)]
+#[derive(Debug)]
+pub struct MockReceiver(pub ReceiverReply);
+
+#[cfg(test)]
+impl WasmProgram for MockReceiver {
+    fn init(&mut self, _payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Ok(None)
+    }
+    fn handle(&mut self, _payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Ok(Some(self.0.encode()))
+    }
+    fn handle_reply(&mut self, _payload: Vec<u8>) -> Result<(), &'static str> {
+        Ok(())
+    }
+    fn handle_signal(&mut self, _payload: Vec<u8>) -> Result<(), &'static str> {
+        Ok(())
+    }
+    fn state(&mut self) -> Result<Vec<u8>, &'static str> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+pub fn init_receiver(system: &System, id: u64, reply: ReceiverReply, gm: &Program) {
+    let receiver = Program::mock_with_id(system, id, MockReceiver(reply));
+    receiver.send_bytes(42, []);
+    gm.send(id, Action::RegisterReceiver);
+}
+
 #[test]
 fn mint_works() {
     let system = System::new();
@@ -253,6 +285,7 @@ fn update_token_metadata_works() {
         description: "nft for test".to_string(),
         image_uri: "https://gm.dev/nft.png".to_string(),
         json_uri: "https://gm.dev/nft.json".to_string(),
+        ..Default::default()
     });
 
     let res = program.send(
@@ -299,3 +332,807 @@ fn set_approval_for_all_works() {
     assert_eq!(res.log().len(), 1);
     assert_eq!(res.log()[0].payload(), expected.encode());
 }
+
+#[test]
+fn approve_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+
+    program.send(42, Init::default());
+    init_program(&program);
+
+    let res = program.send(
+        42,
+        Action::Approve {
+            operator: ActorId::from(1),
+            token: 2,
+            amount: 3,
+        },
+    );
+
+    let expected = Event::Approval {
+        owner: ActorId::from(42),
+        operator: ActorId::from(1),
+        token: 2,
+        amount: 3,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn permit_for_all_works() {
+    use blake2::{digest::consts::U32, Blake2b, Digest};
+
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let keypair = schnorrkel::MiniSecretKey::from_bytes(&[7; 32])
+        .unwrap()
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+    let owner = ActorId::from(keypair.public.to_bytes());
+    let operator = ActorId::from(7);
+
+    let domain: [u8; 32] =
+        Blake2b::<U32>::digest((b"gm/permit-for-all".as_slice(), ActorId::from(1)).encode()).into();
+    let message: [u8; 32] =
+        Blake2b::<U32>::digest((domain, owner, operator, true, u64::MAX, 0u64).encode()).into();
+    let signature = keypair.sign_simple(b"substrate", &message).to_bytes();
+
+    let res = program.send(
+        42,
+        Action::PermitForAll {
+            owner,
+            operator,
+            approved: true,
+            deadline: u64::MAX,
+            nonce: 0,
+            signature: Signature::Sr25519(signature),
+        },
+    );
+
+    let expected = Event::ApprovedForAll {
+        owner,
+        operator,
+        approved: true,
+    };
+
+    assert_eq!(res.log().len(), 3);
+    assert!(res.contains(&Log::builder().dest(42).payload(expected.clone())));
+    assert!(res.contains(&Log::builder().dest(7).payload(expected)));
+
+    let res = program.send(
+        42,
+        Action::PermitForAll {
+            owner,
+            operator,
+            approved: true,
+            deadline: u64::MAX,
+            nonce: 0,
+            signature: Signature::Sr25519(signature),
+        },
+    );
+
+    let expected = Event::Error(Error::InvalidNonce);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn execute_signed_works() {
+    use blake2::{digest::consts::U32, Blake2b, Digest};
+
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let keypair = schnorrkel::MiniSecretKey::from_bytes(&[7; 32])
+        .unwrap()
+        .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519);
+    let signer = ActorId::from(keypair.public.to_bytes());
+
+    program.send(
+        42,
+        Action::Mint {
+            to: signer,
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let action = SignedAction::TransferFrom {
+        from: signer,
+        to: ActorId::from(7),
+        token: 0,
+        amount: 1,
+    };
+    let domain: [u8; 32] =
+        Blake2b::<U32>::digest((b"gm/execute-signed".as_slice(), ActorId::from(1)).encode()).into();
+    let message: [u8; 32] =
+        Blake2b::<U32>::digest((domain, signer, 0u64, u64::MAX, action.encode()).encode()).into();
+    let signature = keypair.sign_simple(b"substrate", &message).to_bytes();
+
+    let res = program.send(
+        42,
+        Action::ExecuteSigned {
+            action,
+            signer,
+            nonce: 0,
+            deadline: u64::MAX,
+            signature: Signature::Sr25519(signature),
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: signer,
+        from: signer,
+        to: ActorId::from(7),
+        token: 0,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 3);
+    assert!(res.contains(&Log::builder().dest(42).payload(expected.clone())));
+    assert!(res.contains(&Log::builder().dest(7).payload(expected)));
+}
+
+#[test]
+fn buy_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    const PRICE: u128 = 1_000_000_000_000;
+    system.mint_to(7, 5 * PRICE);
+
+    program.send(
+        42,
+        Action::CreateToken {
+            token: 0,
+            policy: MintPolicy::Unlimited,
+        },
+    );
+    let res = program.send(
+        42,
+        Action::SetPrice {
+            token: 0,
+            price: Some(PRICE),
+        },
+    );
+
+    let expected = Event::PriceSet {
+        token: 0,
+        price: Some(PRICE),
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send_with_value(
+        7,
+        Action::Buy {
+            token: 0,
+            amount: 3,
+        },
+        4 * PRICE,
+    );
+
+    let expected = Event::Purchased {
+        buyer: ActorId::from(7),
+        token: 0,
+        amount: 3,
+        cost: 3 * PRICE,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send(42, Action::Withdraw);
+
+    let expected = Event::Withdrawn {
+        to: ActorId::from(42),
+        amount: 3 * PRICE,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn set_token_uri_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 1,
+            amount: 1,
+        },
+    );
+
+    let res = program.send(
+        42,
+        Action::SetTokenUri {
+            token: 1,
+            uri: Some("ipfs://nft/{id}.json".to_string()),
+        },
+    );
+
+    let expected = Event::URI {
+        value: "ipfs://nft/0000000000000000000000000000000000000000000000000000000000000001.json"
+            .to_string(),
+        token: 1,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn freeze_metadata_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 1,
+            amount: 1,
+        },
+    );
+
+    let res = program.send(42, Action::FreezeMetadata { token: 1 });
+
+    let expected = Event::PermanentURI {
+        value: "https://gm.dev/0000000000000000000000000000000000000000000000000000000000000001"
+            .to_string(),
+        token: 1,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send(
+        42,
+        Action::SetTokenUri {
+            token: 1,
+            uri: Some("ipfs://nft/{id}.json".to_string()),
+        },
+    );
+
+    let expected = Event::Error(Error::MetadataFrozen);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn grant_role_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let res = program.send(
+        42,
+        Action::GrantRole {
+            account: ActorId::from(1),
+            role: Role::Minter,
+        },
+    );
+
+    let expected = Event::RoleGranted {
+        account: ActorId::from(1),
+        role: Role::Minter,
+        sender: ActorId::from(42),
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send(
+        1,
+        Action::Mint {
+            to: ActorId::from(1),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(1),
+        from: ActorId::zero(),
+        to: ActorId::from(1),
+        token: 0,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn create_token_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let res = program.send(
+        42,
+        Action::CreateToken {
+            token: 0,
+            policy: MintPolicy::Unlimited,
+        },
+    );
+
+    let expected = Event::TokenCreated {
+        token: 0,
+        policy: MintPolicy::Unlimited,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::zero(),
+        to: ActorId::from(42),
+        token: 0,
+        amount: 1,
+    };
+
+    for _ in 0..2 {
+        let res = program.send(
+            42,
+            Action::Mint {
+                to: ActorId::from(42),
+                token: 0,
+                amount: 1,
+            },
+        );
+        assert_eq!(res.log().len(), 1);
+        assert_eq!(res.log()[0].payload(), expected.encode());
+    }
+}
+
+#[test]
+fn subscribe_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let res = program.send(
+        42,
+        Action::Subscribe {
+            subscriber: ActorId::from(7),
+        },
+    );
+
+    let expected = Event::Subscribed {
+        subscriber: ActorId::from(7),
+    };
+
+    // the new subscriber already gets a copy
+    assert_eq!(res.log().len(), 2);
+    assert!(res.contains(&Log::builder().dest(42).payload(expected.clone())));
+    assert!(res.contains(&Log::builder().dest(7).payload(expected)));
+
+    let res = program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::zero(),
+        to: ActorId::from(42),
+        token: 0,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 2);
+    assert!(res.contains(&Log::builder().dest(42).payload(expected.clone())));
+    assert!(res.contains(&Log::builder().dest(7).payload(expected)));
+
+    program.send(
+        7,
+        Action::Unsubscribe {
+            subscriber: ActorId::from(7),
+        },
+    );
+
+    let res = program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 1,
+            amount: 1,
+        },
+    );
+
+    assert_eq!(res.log().len(), 1);
+}
+
+#[test]
+fn transfer_notifies_recipient_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let res = program.send(
+        42,
+        Action::TransferFrom {
+            from: ActorId::from(42),
+            to: ActorId::from(7),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::from(42),
+        to: ActorId::from(7),
+        token: 0,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 2);
+    assert!(res.contains(&Log::builder().dest(42).payload(expected.clone())));
+    assert!(res.contains(&Log::builder().dest(7).payload(expected)));
+}
+
+/// the sample receiver in labs/erc1155-receiver, built along with the workspace
+#[cfg(test)]
+const LAB_RECEIVER: &str = "./target/wasm32-unknown-unknown/release/erc1155_receiver.opt.wasm";
+
+#[cfg(test)]
+fn actor_id(program: &Program) -> ActorId {
+    let id: [u8; 32] = program
+        .id()
+        .as_ref()
+        .try_into()
+        .expect("Invalid program id");
+    ActorId::from(id)
+}
+
+#[test]
+fn lab_receiver_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    // the receiver registers itself with GM during init, rejecting token 7
+    let receiver = Program::from_file(&system, LAB_RECEIVER);
+    receiver.send(42, (actor_id(&program), vec![7u128]));
+    let to = actor_id(&receiver);
+
+    let res = program.send(
+        42,
+        Action::Mint {
+            to,
+            token: 1,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::zero(),
+        to,
+        token: 1,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send(
+        42,
+        Action::Mint {
+            to,
+            token: 7,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::Error(Error::ReceiverRejected);
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn transfer_to_receiver_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+    init_receiver(&system, 100, ReceiverReply::Accepted, &program);
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let res = program.send(
+        42,
+        Action::TransferFrom {
+            from: ActorId::from(42),
+            to: ActorId::from(100),
+            token: 0,
+            amount: 1,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::from(42),
+        to: ActorId::from(100),
+        token: 0,
+        amount: 1,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn mint_batch_to_receiver_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+    init_receiver(&system, 100, ReceiverReply::Accepted, &program);
+
+    let res = program.send(
+        42,
+        Action::MintBatch {
+            to: ActorId::from(100),
+            token: vec![0, 1],
+            amount: vec![1, 2],
+        },
+    );
+
+    let expected = Event::TransferBatch {
+        operator: ActorId::from(42),
+        from: ActorId::zero(),
+        to: ActorId::from(100),
+        token: vec![0, 1],
+        amount: vec![1, 2],
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn buy_listing_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    const PRICE: u128 = 1_000_000_000_000;
+    system.mint_to(7, 5 * PRICE);
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 1,
+            amount: 5,
+        },
+    );
+    let res = program.send(
+        42,
+        Action::List {
+            token: 1,
+            amount: 3,
+            price: PRICE,
+        },
+    );
+
+    let expected = Event::Listed {
+        listing_id: 0,
+        seller: ActorId::from(42),
+        token: 1,
+        amount: 3,
+        price: PRICE,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    let res = program.send_with_value(
+        7,
+        Action::BuyListing {
+            listing_id: 0,
+            amount: 2,
+        },
+        3 * PRICE,
+    );
+
+    let expected = Event::Sold {
+        listing_id: 0,
+        buyer: ActorId::from(7),
+        seller: ActorId::from(42),
+        amount: 2,
+        cost: 2 * PRICE,
+    };
+
+    assert_eq!(res.log().len(), 2);
+    assert!(res.contains(&Log::builder().dest(7).payload(expected.clone())));
+    assert!(res.contains(&Log::builder().dest(42).payload(expected)));
+
+    let res = program.send(42, Action::Cancel { listing_id: 0 });
+
+    let expected = Event::Cancelled { listing_id: 0 };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn set_token_royalty_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    program.send(
+        42,
+        Init {
+            name: "gm".to_string(),
+            symbol: "GM".to_string(),
+            base_uri: "https://gm.dev/{}".to_string(),
+            royalty: Some(Royalty {
+                receiver: ActorId::from(42),
+                basis_points: 250,
+            }),
+        },
+    );
+
+    program.send(
+        42,
+        Action::Mint {
+            to: ActorId::from(42),
+            token: 1,
+            amount: 1,
+        },
+    );
+    let royalty = Some(Royalty {
+        receiver: ActorId::from(7),
+        basis_points: 1000,
+    });
+    let res = program.send(42, Action::SetTokenRoyalty { token: 1, royalty });
+
+    let expected = Event::TokenRoyaltySet { token: 1, royalty };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+}
+
+#[test]
+fn whoami_works() {
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let res = program.send(42, Action::Whoami);
+
+    assert_eq!(res.log().len(), 1);
+    let event = Event::decode(&mut res.log()[0].payload()).expect("Invalid Whoami event");
+    assert!(matches!(
+        event,
+        Event::Whoami { sender, origin, .. } if sender == ActorId::from(42) && origin == ActorId::from(42)
+    ));
+}
+
+#[test]
+fn import_snapshot_works() {
+    use blake2::{digest::consts::U32, Blake2b, Digest};
+
+    let system = System::new();
+    system.init_logger();
+
+    let program = Program::current(&system);
+    init_program(&program);
+
+    let entries = vec![
+        SnapshotEntry::Balance {
+            token: 1,
+            owner: ActorId::from(7),
+            amount: 10,
+        },
+        SnapshotEntry::Approval {
+            owner: ActorId::from(7),
+            operator: ActorId::from(42),
+        },
+    ];
+    let checksum = entries.iter().fold([0u8; 32], |checksum, entry| {
+        Blake2b::<U32>::digest((checksum, entry).encode()).into()
+    });
+
+    program.send(42, Action::Pause);
+    let res = program.send(42, Action::ImportSnapshot(entries));
+
+    let expected = Event::SnapshotImported {
+        entries: 2,
+        checksum,
+    };
+
+    assert_eq!(res.log().len(), 1);
+    assert_eq!(res.log()[0].payload(), expected.encode());
+
+    program.send(42, Action::Unpause);
+    let res = program.send(
+        42,
+        Action::TransferFrom {
+            from: ActorId::from(7),
+            to: ActorId::from(42),
+            token: 1,
+            amount: 4,
+        },
+    );
+
+    let expected = Event::TransferSingle {
+        operator: ActorId::from(42),
+        from: ActorId::from(7),
+        to: ActorId::from(42),
+        token: 1,
+        amount: 4,
+    };
+
+    assert!(res.contains(&Log::builder().dest(42).payload(expected)));
+}
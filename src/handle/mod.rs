@@ -5,40 +5,103 @@ use crate::*;
 mod handle_panic_test;
 mod handle_test;
 
-#[no_mangle]
-unsafe extern "C" fn handle() {
-    let sender: ActorId = gstd::msg::source();
-    let state = STATE.as_mut().expect("Could not get state");
+#[gstd::async_main]
+async fn main() {
+    let state = unsafe { STATE.as_mut().expect("Could not get state") };
     let action: Action = gstd::msg::load().expect("Could not load msg");
+    if let Err(error) = process(state, action).await {
+        state.emit_error_event(error);
+    }
+}
+
+/// performs the action and emits the resulting event
+///
+/// checks are performed before any state mutation, so an error leaves the
+/// state untouched
+async fn process(state: &mut Contract<GearConfig>, action: Action) -> Result<(), Error> {
+    let sender: ActorId = gstd::msg::source();
+    if gstd::msg::value() > 0 && !matches!(action, Action::Buy { .. } | Action::BuyListing { .. }) {
+        return Err(Error::NotPayable);
+    }
     match action {
         Action::TransferFrom {
             from,
             to,
             token,
             amount,
-        } => {
-            state.safe_transfer_from(from, to, token, amount);
-            state.emit_transfer_single_event(sender, from, to, token, amount);
-        }
+        } => transfer_from(state, from, to, token, amount).await?,
         Action::BatchTransferFrom {
             from,
             to,
             token,
             amount,
-        } => {
-            state.safe_batch_transfer_from(from, to, token.clone(), amount.clone());
-            state.emit_transfer_batch_event(sender, from, to, token, amount);
-        }
+        } => batch_transfer_from(state, from, to, token, amount).await?,
         Action::SetApprovalForAll { operator, approved } => {
-            state.set_approval_for_all(sender, operator, approved);
+            state.set_approval_for_all(sender, operator, approved)?;
             state.emit_approval_for_all_event(sender, operator, approved);
         }
+        Action::Approve {
+            operator,
+            token,
+            amount,
+        } => {
+            state.approve(sender, operator, token, amount)?;
+            state.emit_approval_event(sender, operator, token, amount);
+        }
+        Action::PermitForAll {
+            owner,
+            operator,
+            approved,
+            deadline,
+            nonce,
+            signature,
+        } => {
+            state.permit_for_all(owner, operator, approved, deadline, nonce, signature)?;
+            state.emit_approval_for_all_event(owner, operator, approved);
+        }
+        Action::ExecuteSigned {
+            action,
+            signer,
+            nonce,
+            deadline,
+            signature,
+        } => {
+            state.enter_signed(signer, nonce, deadline, &action.encode(), signature)?;
+            let result = match action {
+                SignedAction::TransferFrom {
+                    from,
+                    to,
+                    token,
+                    amount,
+                } => transfer_from(state, from, to, token, amount).await,
+                SignedAction::BatchTransferFrom {
+                    from,
+                    to,
+                    token,
+                    amount,
+                } => batch_transfer_from(state, from, to, token, amount).await,
+            };
+            state.exit_signed(signer, nonce, result.is_ok());
+            result?
+        }
         Action::Mint { to, token, amount } => {
-            state.mint(to, token, amount);
+            state.check_mint(to, token, amount)?;
+            notify_received(state, sender, ActorId::zero(), to, token, amount).await?;
+            state.mint(to, token, amount)?;
             state.emit_transfer_single_event(sender, ActorId::zero(), to, token, amount);
         }
         Action::MintBatch { to, token, amount } => {
-            state.mint_batch(to, token.clone(), amount.clone());
+            state.check_mint_batch(to, token.clone(), amount.clone())?;
+            notify_batch_received(
+                state,
+                sender,
+                ActorId::zero(),
+                to,
+                token.clone(),
+                amount.clone(),
+            )
+            .await?;
+            state.mint_batch(to, token.clone(), amount.clone())?;
             state.emit_transfer_batch_event(sender, ActorId::zero(), to, token, amount);
         }
         Action::Burn {
@@ -46,7 +109,7 @@ unsafe extern "C" fn handle() {
             token,
             amount,
         } => {
-            state.burn(from, token, amount);
+            state.burn(from, token, amount)?;
             state.emit_transfer_single_event(sender, from, ActorId::zero(), token, amount);
         }
         Action::BurnBatch {
@@ -54,13 +117,248 @@ unsafe extern "C" fn handle() {
             token,
             amount,
         } => {
-            state.burn_batch(from, token.clone(), amount.clone());
+            state.burn_batch(from, token.clone(), amount.clone())?;
             state.emit_transfer_batch_event(sender, from, ActorId::zero(), token, amount);
         }
         Action::UpdateTokenMetadata { token, metadata } => {
-            state.update_token_metadata(token, metadata.clone());
+            state.update_token_metadata(token, metadata.clone())?;
             state.emit_update_token_metadata_event(token, metadata);
         }
+        Action::SetTokenUri { token, uri } => {
+            state.set_token_uri(token, uri)?;
+            state.emit_uri_event(state.uri(token), token);
+        }
+        Action::FreezeMetadata { token } => {
+            state.freeze_metadata(token)?;
+            state.emit_permanent_uri_event(state.uri(token), token);
+        }
+        Action::GrantRole { account, role } => {
+            state.grant_role(account, role)?;
+            state.emit_role_granted_event(account, role, sender);
+        }
+        Action::RevokeRole { account, role } => {
+            state.revoke_role(account, role)?;
+            state.emit_role_revoked_event(account, role, sender);
+        }
+        Action::RenounceRole { role } => {
+            state.renounce_role(sender, role)?;
+            state.emit_role_revoked_event(sender, role, sender);
+        }
+        Action::TransferOwnership { new_owner } => {
+            let previous_owner = state.owner();
+            state.transfer_ownership(new_owner)?;
+            state.emit_ownership_transferred_event(previous_owner, new_owner);
+        }
+        Action::CreateToken { token, policy } => {
+            state.create_token(token, policy)?;
+            state.emit_token_created_event(token, policy);
+        }
+        Action::RegisterReceiver => {
+            state.register_receiver(sender)?;
+            state.emit_receiver_registered_event(sender);
+        }
+        Action::UnregisterReceiver => {
+            state.unregister_receiver(sender)?;
+            state.emit_receiver_unregistered_event(sender);
+        }
+        Action::Pause => {
+            state.pause()?;
+            state.emit_paused_event(sender);
+        }
+        Action::Unpause => {
+            state.unpause()?;
+            state.emit_unpaused_event(sender);
+        }
+        Action::PauseToken { token } => {
+            state.pause_token(token)?;
+            state.emit_token_paused_event(token, sender);
+        }
+        Action::UnpauseToken { token } => {
+            state.unpause_token(token)?;
+            state.emit_token_unpaused_event(token, sender);
+        }
+        Action::Subscribe { subscriber } => {
+            state.subscribe(subscriber)?;
+            state.emit_subscribed_event(subscriber);
+        }
+        Action::Unsubscribe { subscriber } => {
+            state.unsubscribe(subscriber)?;
+            state.emit_unsubscribed_event(subscriber);
+        }
+        Action::SetPrice { token, price } => {
+            state.set_price(token, price)?;
+            state.emit_price_set_event(token, price);
+        }
+        Action::Buy { token, amount } => {
+            state.check_buy(token, amount)?;
+            notify_received(state, sender, ActorId::zero(), sender, token, amount).await?;
+            let (cost, refund) = state.buy(token, amount)?;
+            state.emit_purchased_event(sender, token, amount, cost, refund);
+        }
+        Action::Withdraw => {
+            let amount = state.withdraw()?;
+            state.emit_withdrawn_event(sender, amount);
+        }
+        Action::MintVested {
+            to,
+            token,
+            amount,
+            start,
+            cliff,
+            duration,
+        } => {
+            state.check_mint_vested(to, token, amount, start, cliff, duration)?;
+            notify_received(state, sender, ActorId::zero(), to, token, amount).await?;
+            state.mint_vested(to, token, amount, start, cliff, duration)?;
+            state.emit_transfer_single_event(sender, ActorId::zero(), to, token, amount);
+        }
+        Action::Release { token } => {
+            let amount = state.release(token)?;
+            state.emit_released_event(sender, token, amount);
+        }
+        Action::ImportSnapshot(entries) => {
+            let count = entries.len() as u32;
+            let checksum = state.import_snapshot(entries)?;
+            state.emit_snapshot_imported_event(count, checksum);
+        }
+        Action::List {
+            token,
+            amount,
+            price,
+        } => {
+            let listing_id = state.list(token, amount, price)?;
+            state.emit_listed_event(listing_id, sender, token, amount, price);
+        }
+        Action::Cancel { listing_id } => {
+            state.cancel(listing_id)?;
+            state.emit_cancelled_event(listing_id);
+        }
+        Action::BuyListing { listing_id, amount } => {
+            state.check_buy_listing(listing_id, amount)?;
+            let listing = state.listing(listing_id).ok_or(Error::NoSuchListing)?;
+            notify_received(state, sender, listing.seller, sender, listing.token, amount).await?;
+            let (seller, cost, refund) = state.buy_listing(listing_id, amount)?;
+            state.emit_sold_event(listing_id, sender, seller, amount, cost, refund);
+        }
+        Action::SetDefaultRoyalty { royalty } => {
+            state.set_default_royalty(royalty)?;
+            state.emit_default_royalty_set_event(royalty);
+        }
+        Action::SetTokenRoyalty { token, royalty } => {
+            state.set_token_royalty(token, royalty)?;
+            state.emit_token_royalty_set_event(token, royalty);
+        }
         Action::Whoami => state.emit_whoami_event(),
     }
+    Ok(())
+}
+
+/// transfers on behalf of the sender, which is the signer of a meta-transaction
+async fn transfer_from(
+    state: &mut Contract<GearConfig>,
+    from: ActorId,
+    to: ActorId,
+    token: u128,
+    amount: u128,
+) -> Result<(), Error> {
+    let sender = state.sender();
+    state.check_transfer_from(from, to, token, amount)?;
+    notify_received(state, sender, from, to, token, amount).await?;
+    state.safe_transfer_from(from, to, token, amount)?;
+    state.emit_transfer_single_event(sender, from, to, token, amount);
+    Ok(())
+}
+
+/// batch counterpart of `transfer_from`
+async fn batch_transfer_from(
+    state: &mut Contract<GearConfig>,
+    from: ActorId,
+    to: ActorId,
+    token: Vec<u128>,
+    amount: Vec<u128>,
+) -> Result<(), Error> {
+    let sender = state.sender();
+    state.check_batch_transfer_from(from, to, token.clone(), amount.clone())?;
+    notify_batch_received(state, sender, from, to, token.clone(), amount.clone()).await?;
+    state.safe_batch_transfer_from(from, to, token.clone(), amount.clone())?;
+    state.emit_transfer_batch_event(sender, from, to, token, amount);
+    Ok(())
+}
+
+/// blocks a receiver has to reply before the tokens count as rejected, so
+/// that an awaiting message, and the signer it may act for, is never left
+/// behind
+const RECEIVER_REPLY_BLOCKS: u32 = 100;
+
+/// asks a registered receiver program to accept incoming tokens
+///
+/// called after the checks and before any balance is changed, so a rejected
+/// or failed receiver aborts the transaction without side effects. since
+/// other messages can be processed while waiting for the reply, the
+/// mutation that follows performs its checks again.
+async fn notify_received(
+    state: &Contract<GearConfig>,
+    operator: ActorId,
+    from: ActorId,
+    to: ActorId,
+    token: u128,
+    amount: u128,
+) -> Result<(), Error> {
+    if !state.is_receiver(to) {
+        return Ok(());
+    }
+    let reply: ReceiverReply = gstd::msg::send_for_reply_as(
+        to,
+        ReceiverAction::OnERC1155Received {
+            operator,
+            from,
+            token,
+            amount,
+        },
+        0,
+        0,
+    )
+    .expect("Failed to send ReceiverAction::OnERC1155Received")
+    .up_to(Some(RECEIVER_REPLY_BLOCKS))
+    .expect("Failed to set the receiver reply timeout")
+    .await
+    .unwrap_or(ReceiverReply::Rejected);
+    match reply {
+        ReceiverReply::Accepted => Ok(()),
+        ReceiverReply::Rejected => Err(Error::ReceiverRejected),
+    }
+}
+
+/// batch version of `notify_received`
+async fn notify_batch_received(
+    state: &Contract<GearConfig>,
+    operator: ActorId,
+    from: ActorId,
+    to: ActorId,
+    token: Vec<u128>,
+    amount: Vec<u128>,
+) -> Result<(), Error> {
+    if !state.is_receiver(to) {
+        return Ok(());
+    }
+    let reply: ReceiverReply = gstd::msg::send_for_reply_as(
+        to,
+        ReceiverAction::OnERC1155BatchReceived {
+            operator,
+            from,
+            token,
+            amount,
+        },
+        0,
+        0,
+    )
+    .expect("Failed to send ReceiverAction::OnERC1155BatchReceived")
+    .up_to(Some(RECEIVER_REPLY_BLOCKS))
+    .expect("Failed to set the receiver reply timeout")
+    .await
+    .unwrap_or(ReceiverReply::Rejected);
+    match reply {
+        ReceiverReply::Accepted => Ok(()),
+        ReceiverReply::Rejected => Err(Error::ReceiverRejected),
+    }
 }
@@ -16,6 +16,7 @@ fn init_works() {
         name: "gm".to_string(),
         symbol: "GM".to_string(),
         base_uri: "https://gm.dev/{}".to_string(),
+        royalty: None,
     };
 
     let res = program.send(42, init_msg);
@@ -11,6 +11,7 @@ pub unsafe extern "C" fn init() {
         name,
         symbol,
         base_uri,
+        royalty,
     } = gstd::msg::load().expect("Invalid init message");
     let id: ActorId = gstd::msg::source();
     STATE = Some(Contract::<GearConfig>::new(&id));
@@ -18,5 +19,8 @@ pub unsafe extern "C" fn init() {
     state.name = name;
     state.symbol = symbol;
     state.base_uri = base_uri;
+    state
+        .set_default_royalty(royalty)
+        .expect("Invalid default royalty");
     gstd::msg::reply(InitOk, 0).expect("Failed to reply InitOk");
 }
@@ -7,8 +7,15 @@ pub static mut STATE: Option<Contract<GearConfig>> = None;
 
 #[no_mangle]
 extern "C" fn state() {
-    let query: Query = gstd::msg::load().expect("failed to decode input argument");
     let state = unsafe { STATE.as_ref().expect("failed to get contract state") };
+    // an empty payload reads the whole state, tagged with its layout version
+    // for the metawasm readers in `state`
+    if gstd::msg::size() == 0 {
+        let dump = VersionedState::from(state.clone());
+        gstd::msg::reply(dump, 0).expect("Failed to share state");
+        return;
+    }
+    let query: Query = gstd::msg::load().expect("failed to decode input argument");
     let reply = match query {
         Query::Name => State::Name(state.name()),
         Query::Symbol => State::Symbol(state.symbol()),
@@ -17,6 +24,54 @@ extern "C" fn state() {
             let metadata = state.get_token_metadata(token);
             State::TokenMetadata(metadata)
         }
+        Query::Uri(token) => State::Uri(state.uri(token)),
+        Query::TotalSupply(token) => State::TotalSupply(state.total_supply(token)),
+        Query::Exists(token) => State::Exists(state.exists(token)),
+        Query::Owner => State::Owner(state.owner()),
+        Query::HasRole { account, role } => State::HasRole(state.has_role(account, role)),
+        Query::MintPolicy(token) => State::MintPolicy(state.mint_policy(token)),
+        Query::Allowance {
+            owner,
+            operator,
+            token,
+        } => State::Allowance(state.allowance(owner, operator, token)),
+        Query::IsPaused => State::IsPaused(state.is_paused()),
+        Query::IsTokenPaused(token) => State::IsTokenPaused(state.is_token_paused(token)),
+        Query::IsMetadataFrozen(token) => State::IsMetadataFrozen(state.is_metadata_frozen(token)),
+        Query::Nonce(owner) => State::Nonce(state.nonce(owner)),
+        Query::DomainSeparator => State::DomainSeparator(state.domain_separator()),
+        Query::Price(token) => State::Price(state.price(token)),
+        Query::Proceeds => State::Proceeds(state.proceeds()),
+        Query::Listings {
+            token,
+            offset,
+            limit,
+        } => State::Listings(state.listings(token, offset, limit)),
+        Query::RoyaltyInfo { token, sale_price } => {
+            let (receiver, amount) = state.royalty_info(token, sale_price);
+            State::RoyaltyInfo(receiver, amount)
+        }
+        Query::Tokens { offset, limit } => State::Tokens(state.tokens(offset, limit)),
+        Query::HoldersOf {
+            token,
+            offset,
+            limit,
+        } => State::HoldersOf(state.holders_of(token, offset, limit)),
+        Query::TokensOf {
+            owner,
+            offset,
+            limit,
+        } => State::TokensOf(state.tokens_of(owner, offset, limit)),
+        Query::LockedBalance { owner, token } => {
+            State::LockedBalance(state.locked_balance_of(owner, token))
+        }
+        Query::VestingSchedules(owner) => State::VestingSchedules(state.vesting_schedules(owner)),
+        Query::Snapshot { cursor, limit } => {
+            let (entries, next) = state.snapshot(cursor, limit);
+            State::Snapshot(entries, next)
+        }
+        Query::SnapshotChecksum => State::SnapshotChecksum(state.snapshot_checksum()),
+        Query::Version => State::Version(state.version()),
         Query::IsApprovedForAll { owner, operator } => {
             let approved = state.is_approved_for_all(owner, operator);
             State::IsApprovedForAll(approved)
@@ -25,10 +80,10 @@ extern "C" fn state() {
             let balance = state.balance_of(who, token);
             State::BalanceOf(balance)
         }
-        Query::BalanceOfBatch(who, token) => {
-            let balance = state.balance_of_batch(who, token);
-            State::BalanceOfBatch(balance)
-        }
+        Query::BalanceOfBatch(who, token) => match state.balance_of_batch(who, token) {
+            Ok(balance) => State::BalanceOfBatch(balance),
+            Err(error) => State::Error(error),
+        },
     };
     gstd::msg::reply(reply, 0).expect("Failed to share state");
 }
@@ -1,53 +1,221 @@
 #![no_std]
 
 use config::GearConfig;
-use gmeta::{metawasm, Metadata};
+use contract::VersionedState;
+use gmeta::metawasm;
 use gstd::prelude::*;
 use interface::*;
-use io::TokenMetadata;
-use metadata::ProgramMetadata;
+use io::{Error, Listing, MintPolicy, Role, SnapshotEntry, Stats, TokenMetadata, VestingSchedule};
 
 #[cfg(feature = "binary-vendor")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 #[metawasm]
 pub mod metafns {
-    pub type State = <ProgramMetadata as Metadata>::State;
+    /// whole state read with an empty payload, migrated to the latest layout
+    /// before it is read
+    pub type State = VersionedState<GearConfig>;
 
     pub fn name(state: State) -> String {
-        state.name()
+        state.into_latest().name()
     }
     pub fn symbol(state: State) -> String {
-        state.symbol()
+        state.into_latest().symbol()
     }
     pub fn base_uri(state: State) -> String {
-        state.base_uri
+        state.into_latest().base_uri
+    }
+    pub fn uri(state: State, token: <GearConfig as IConfig>::TokenId) -> String {
+        state.into_latest().uri(token)
+    }
+    pub fn total_supply(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+    ) -> <GearConfig as IConfig>::Balance {
+        state.into_latest().total_supply(token)
+    }
+    pub fn exists(state: State, token: <GearConfig as IConfig>::TokenId) -> bool {
+        state.into_latest().exists(token)
+    }
+    pub fn nonce(state: State, owner: <GearConfig as IConfig>::AccountId) -> u64 {
+        state.into_latest().nonce(owner)
+    }
+    pub fn price(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+    ) -> Option<<GearConfig as IConfig>::Balance> {
+        state.into_latest().price(token)
+    }
+    pub fn proceeds(state: State) -> <GearConfig as IConfig>::Balance {
+        state.into_latest().proceeds()
+    }
+    pub fn listings(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(u64, Listing)> {
+        state.into_latest().listings(token, offset, limit)
+    }
+    pub fn locked_balance_of(
+        state: State,
+        owner: <GearConfig as IConfig>::AccountId,
+        token: <GearConfig as IConfig>::TokenId,
+    ) -> <GearConfig as IConfig>::Balance {
+        state.into_latest().locked_balance_of(owner, token)
+    }
+    pub fn vesting_schedules(
+        state: State,
+        owner: <GearConfig as IConfig>::AccountId,
+    ) -> Vec<VestingSchedule> {
+        state.into_latest().vesting_schedules(owner)
+    }
+    /// layout version of the dump, before any migration
+    pub fn version(state: State) -> u32 {
+        state.version()
+    }
+    pub fn snapshot(state: State, cursor: u32, limit: u32) -> (Vec<SnapshotEntry>, Option<u32>) {
+        state.into_latest().snapshot(cursor, limit)
+    }
+    pub fn snapshot_checksum(state: State) -> [u8; 32] {
+        state.into_latest().snapshot_checksum()
+    }
+    pub fn royalty_info(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+        sale_price: <GearConfig as IConfig>::Balance,
+    ) -> (
+        <GearConfig as IConfig>::AccountId,
+        <GearConfig as IConfig>::Balance,
+    ) {
+        state.into_latest().royalty_info(token, sale_price)
+    }
+    pub fn is_paused(state: State) -> bool {
+        state.into_latest().is_paused()
+    }
+    pub fn is_token_paused(state: State, token: <GearConfig as IConfig>::TokenId) -> bool {
+        state.into_latest().is_token_paused(token)
+    }
+    pub fn is_metadata_frozen(state: State, token: <GearConfig as IConfig>::TokenId) -> bool {
+        state.into_latest().is_metadata_frozen(token)
+    }
+    pub fn creator(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+    ) -> Option<<GearConfig as IConfig>::AccountId> {
+        state.into_latest().creator(token)
+    }
+    pub fn tokens(state: State, offset: u32, limit: u32) -> Vec<<GearConfig as IConfig>::TokenId> {
+        state.into_latest().tokens(offset, limit)
+    }
+    pub fn holders_of(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(
+        <GearConfig as IConfig>::AccountId,
+        <GearConfig as IConfig>::Balance,
+    )> {
+        state.into_latest().holders_of(token, offset, limit)
+    }
+    pub fn tokens_of(
+        state: State,
+        owner: <GearConfig as IConfig>::AccountId,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(
+        <GearConfig as IConfig>::TokenId,
+        <GearConfig as IConfig>::Balance,
+    )> {
+        state.into_latest().tokens_of(owner, offset, limit)
+    }
+    pub fn all_balances(
+        state: State,
+    ) -> Vec<(
+        <GearConfig as IConfig>::TokenId,
+        <GearConfig as IConfig>::AccountId,
+        <GearConfig as IConfig>::Balance,
+    )> {
+        state.into_latest().all_balances()
+    }
+    pub fn balances_of_owner(
+        state: State,
+        owner: <GearConfig as IConfig>::AccountId,
+    ) -> Vec<(
+        <GearConfig as IConfig>::TokenId,
+        <GearConfig as IConfig>::Balance,
+    )> {
+        state.into_latest().balances_of_owner(owner)
+    }
+    /// every holder of the token, see `holders_of` for pages
+    pub fn all_holders_of(
+        state: State,
+        token: <GearConfig as IConfig>::TokenId,
+    ) -> Vec<(
+        <GearConfig as IConfig>::AccountId,
+        <GearConfig as IConfig>::Balance,
+    )> {
+        state.into_latest().all_holders_of(token)
+    }
+    pub fn all_approvals(
+        state: State,
+        owner: <GearConfig as IConfig>::AccountId,
+    ) -> Vec<<GearConfig as IConfig>::AccountId> {
+        state.into_latest().all_approvals(owner)
+    }
+    pub fn all_token_metadata(
+        state: State,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(<GearConfig as IConfig>::TokenId, TokenMetadata)> {
+        state.into_latest().all_token_metadata(offset, limit)
+    }
+    pub fn stats(state: State) -> Stats {
+        state.into_latest().stats()
+    }
+    pub fn owner(state: State) -> <GearConfig as IConfig>::AccountId {
+        state.into_latest().owner()
+    }
+    pub fn has_role(state: State, account: <GearConfig as IConfig>::AccountId, role: Role) -> bool {
+        state.into_latest().has_role(account, role)
+    }
+    pub fn mint_policy(state: State, token: <GearConfig as IConfig>::TokenId) -> MintPolicy {
+        state.into_latest().mint_policy(token)
     }
     pub fn token_metadata(
         state: State,
         token: <GearConfig as IConfig>::TokenId,
     ) -> Option<TokenMetadata> {
-        state.get_token_metadata(token)
+        state.into_latest().get_token_metadata(token)
     }
     pub fn is_approved_for_all(
         state: State,
         owner: <GearConfig as IConfig>::AccountId,
         operator: <GearConfig as IConfig>::AccountId,
     ) -> bool {
-        state.is_approved_for_all(owner, operator)
+        state.into_latest().is_approved_for_all(owner, operator)
+    }
+    pub fn allowance(
+        state: State,
+        owner: <GearConfig as IConfig>::AccountId,
+        operator: <GearConfig as IConfig>::AccountId,
+        token: <GearConfig as IConfig>::TokenId,
+    ) -> <GearConfig as IConfig>::Balance {
+        state.into_latest().allowance(owner, operator, token)
     }
     pub fn balance_of(
         state: State,
         who: <GearConfig as IConfig>::AccountId,
         token: <GearConfig as IConfig>::TokenId,
     ) -> <GearConfig as IConfig>::Balance {
-        state.balance_of(who, token)
+        state.into_latest().balance_of(who, token)
     }
     pub fn balance_of_batch(
         state: State,
         who: Vec<<GearConfig as IConfig>::AccountId>,
         token: Vec<<GearConfig as IConfig>::TokenId>,
-    ) -> Vec<<GearConfig as IConfig>::Balance> {
-        state.balance_of_batch(who, token)
+    ) -> Result<Vec<<GearConfig as IConfig>::Balance>, Error> {
+        state.into_latest().balance_of_batch(who, token)
     }
 }